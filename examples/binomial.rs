@@ -0,0 +1,7 @@
+use competitive::binomial::*;
+
+fn main() {
+    // comb(n, k) mod 10^9 (= 2^9 * 5^9)
+    let cm = CombMod::new(1_000_000_000);
+    assert_eq!(cm.comb(100_000, 31_415), 337_600_000);
+}
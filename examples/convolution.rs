@@ -0,0 +1,21 @@
+use competitive::convolution::*;
+use competitive::mint::*;
+
+fn main() {
+    // (1 + x)^200 = (1 + x)^100 * (1 + x)^100
+    let mut a = vec![Mint998244353::new(1)];
+    for _ in 0..100 {
+        a = convolution(&a, &[Mint998244353::new(1), Mint998244353::new(1)]);
+    }
+    let b = convolution(&a, &a);
+    assert_eq!(b.len(), 201);
+    assert_eq!(b[1], 200);
+    assert_eq!(b[2], 19_900);
+
+    // 1_000_000_007 is not NTT-friendly
+    let a = vec![Mint::new(1_000_000_006); 100];
+    let c = convolution_arbitrary_mod(&a, &a);
+    assert_eq!(c[99], 100);
+
+    assert_eq!(convolution_i64(&[1, -2], &[3, 4]), vec![3, -2, -8]);
+}
@@ -0,0 +1,12 @@
+use competitive::counting::*;
+
+fn main() {
+    // number of ways to split 5 items into 2 groups
+    assert_eq!(stirling2_table::<1_000_000_007>(5)[5][2], 15);
+
+    let p = partition_numbers::<998_244_353>(100);
+    assert_eq!(p[100], 190_569_292);
+
+    // 1^2 + 2^2 + ... + 100^2
+    assert_eq!(power_sum::<998_244_353>(100, 2), 338_350);
+}
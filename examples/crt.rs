@@ -0,0 +1,10 @@
+use competitive::crt::*;
+
+fn main() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+
+    // answer modulo 1_000_000_007 from residues modulo non-coprime moduli
+    let rm = to_coprime(&[(5, 12), (11, 18)]).unwrap();
+    assert_eq!(garner(&rm, 1_000_000_007), 29);
+}
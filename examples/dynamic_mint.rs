@@ -0,0 +1,12 @@
+use competitive::dynamic_mint::*;
+
+fn main() {
+    // modulus read from input
+    let m = 998_244_353;
+    DynamicMint::set_modulus(m);
+
+    let d = DynamicMint::new(10);
+    let ans = d.pow(1_000_000);
+    assert_eq!(ans * ans.inv(), 1);
+    assert_eq!(DynamicMint::modulus(), 998_244_353);
+}
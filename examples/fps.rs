@@ -0,0 +1,17 @@
+use competitive::fps::*;
+use competitive::mint::*;
+
+fn main() {
+    // number of partitions of n = [x^n] prod 1 / (1 - x^k)
+    let n = 100;
+    let mut log = Fps::<998_244_353>::zeros(n + 1);
+    for k in 1..=n {
+        // log(1 / (1 - x^k)) = sum of x^(k * j) / j
+        for j in 1..=n / k {
+            log.0[k * j] += Mint998244353::from(j).inv();
+        }
+    }
+    let p = log.exp(n + 1);
+    assert_eq!(p.0[10], 42);
+    assert_eq!(p.0[100], 190_569_292);
+}
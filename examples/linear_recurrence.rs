@@ -0,0 +1,13 @@
+use competitive::linear_recurrence::*;
+use competitive::mint::*;
+
+fn main() {
+    // tribonacci numbers
+    let mut s = vec![Mint::new(0), Mint::new(0), Mint::new(1)];
+    for i in 3..10 {
+        let next = s[i - 1] + s[i - 2] + s[i - 3];
+        s.push(next);
+    }
+    assert_eq!(berlekamp_massey(&s), vec![Mint::new(1); 3]);
+    assert_eq!(guess_nth_term(&s, 37), 1_132_436_852 % 1_000_000_007);
+}
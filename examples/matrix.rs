@@ -0,0 +1,20 @@
+use competitive::matrix::*;
+use competitive::mint::*;
+
+fn main() {
+    // number of walks of length 10^18 in a triangle graph from a vertex to itself
+    let adj = Matrix::from(vec![
+        vec![Mint::new(0), Mint::new(1), Mint::new(1)],
+        vec![Mint::new(1), Mint::new(0), Mint::new(1)],
+        vec![Mint::new(1), Mint::new(1), Mint::new(0)],
+    ]);
+    let walks = adj.pow(1_000_000_000_000_000_000)[0][0];
+    // (2^n + 2 * (-1)^n) / 3
+    let e = (1_000_000_000_000_000_000u64 % 1_000_000_006) as u32;
+    let expected = (Mint::new(2).pow(e) + Mint::new(2)) / Mint::new(3);
+    assert_eq!(walks, expected);
+
+    let inv = adj.inverse().unwrap();
+    assert_eq!(&adj * &inv, Matrix::identity(3));
+    assert_eq!(adj.determinant(), 2);
+}
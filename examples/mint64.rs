@@ -0,0 +1,10 @@
+use competitive::mint64::*;
+
+fn main() {
+    // rolling hash of a string mod 2^61 - 1
+    let base = Mint61::new(1_000_003);
+    let hash = "competitive"
+        .bytes()
+        .fold(Mint61::new(0), |h, c| h * base + Mint61::new(c as u64));
+    assert_eq!(hash * hash.inv(), 1);
+}
@@ -0,0 +1,14 @@
+use competitive::mint::*;
+use competitive::modular::*;
+
+fn main() {
+    let x = Mint::new(2).sqrt().unwrap();
+    assert_eq!(x * x, 2);
+
+    let x = discrete_log(Mint::new(3), Mint::new(59_049)).unwrap();
+    assert_eq!(x, 10);
+
+    let g = primitive_root(998_244_353);
+    assert_eq!(g, 3);
+    assert_eq!(Mint::new(5).order(), Some(1_000_000_006));
+}
@@ -0,0 +1,30 @@
+use competitive::mint::*;
+use competitive::montgomery::*;
+
+// Compares a long multiply chain on Mint and MontgomeryMint
+// (run with `cargo run --release --example montgomery`)
+fn main() {
+    const N: u32 = 100_000_000;
+
+    let start = std::time::Instant::now();
+    let mut m = Mint::new(1);
+    let step = Mint::new(std::hint::black_box(123_456_789));
+    for _ in 0..N {
+        m *= step;
+        m += step;
+    }
+    let mint_elapsed = start.elapsed();
+
+    let start = std::time::Instant::now();
+    let mut g = MontgomeryMint::new(1);
+    let step = MontgomeryMint::new(std::hint::black_box(123_456_789));
+    for _ in 0..N {
+        g *= step;
+        g += step;
+    }
+    let montgomery_elapsed = start.elapsed();
+
+    assert_eq!(m.value(), g.value());
+    println!("Mint:           {:?}", mint_elapsed);
+    println!("MontgomeryMint: {:?}", montgomery_elapsed);
+}
@@ -0,0 +1,22 @@
+use competitive::fps::*;
+use competitive::mint::*;
+use competitive::polynomial::*;
+use competitive::precalc::*;
+
+fn main() {
+    // f(x) = x^2 + 1
+    let f = Fps(vec![Mint::new(1), Mint::new(0), Mint::new(1)]);
+    let xs = vec![Mint::new(1), Mint::new(2), Mint::new(3)];
+    let ys = evaluate_multipoint(&f, &xs);
+    assert_eq!(ys, vec![Mint::new(2), Mint::new(5), Mint::new(10)]);
+    assert_eq!(interpolate(&xs, &ys), f);
+
+    // 1^3 + 2^3 + ... + n^3 is a polynomial of degree 4 in n
+    let pc: Precalc = Precalc::new(4);
+    let mut ys = vec![Mint::new(0)];
+    for i in 1..=4 {
+        ys.push(ys[i - 1] + Mint::new(i as u32).pow(3));
+    }
+    let n = Mint::new(100);
+    assert_eq!(interpolate_consecutive(&ys, n, &pc), 25_502_500);
+}
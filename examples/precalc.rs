@@ -1,6 +1,11 @@
 use competitive::precalc::*;
 
 fn main() {
-    let pc = Precalc::new(1_000);
+    let pc: Precalc = Precalc::new(1_000);
+    assert_eq!(pc.comb(1_000, 500), 159_835_829);
+
+    // tables can be extended later
+    let mut pc: Precalc = Precalc::default();
+    pc.ensure(1_000);
     assert_eq!(pc.comb(1_000, 500), 159_835_829);
 }
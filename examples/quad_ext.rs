@@ -0,0 +1,11 @@
+use competitive::mint::*;
+use competitive::quad_ext::*;
+
+fn main() {
+    // Fibonacci number by Binet's formula in F_p[sqrt(5)]
+    let half = Mint::new(2).inv();
+    let phi = QuadExt::new(half, half, Mint::new(5));
+    let n = 1_000_000_000_000_000_000;
+    let f = phi.pow(n) - phi.conj().pow(n);
+    assert_eq!(f.b, 209_783_453);
+}
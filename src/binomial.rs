@@ -0,0 +1,141 @@
+use crate::crt::garner;
+use crate::mint::inv_mod;
+use crate::prime::{pow_mod, prime_factors};
+
+// Binomial coefficients modulo any m, computed modulo each prime power of m
+// (by Granville's generalization of Lucas' theorem) and combined by Garner's algorithm
+// (each prime power of m should be small enough to make a table of it, e.g. up to 10^7)
+pub struct CombMod {
+    m: u64,
+    parts: Vec<PrimePower>,
+}
+
+struct PrimePower {
+    p: u64,
+    e: u32,
+    q: u64,
+    // table[i] = product of j (1 <= j <= i, j is not a multiple of p) mod q
+    table: Vec<u64>,
+}
+
+impl PrimePower {
+    fn new(p: u64, e: u32) -> Self {
+        let q = p.pow(e);
+        let mut table = vec![1 % q; q as usize];
+        for i in 1..q as usize {
+            table[i] = if i as u64 % p == 0 {
+                table[i - 1]
+            } else {
+                table[i - 1] * i as u64 % q
+            };
+        }
+        Self { p, e, q, table }
+    }
+
+    // Returns n! with every factor p removed, mod q
+    fn fact_without_p(&self, mut n: u64) -> u64 {
+        let mut ret = 1 % self.q;
+        while n > 0 {
+            // n! = (table[q - 1])^(n / q) * table[n % q] * p^(n / p) * (n / p)!
+            let full = pow_mod(self.table[self.q as usize - 1], n / self.q, self.q);
+            ret = ret * full % self.q * self.table[(n % self.q) as usize] % self.q;
+            n /= self.p;
+        }
+        ret
+    }
+
+    // Returns the exponent of p in n!
+    fn legendre(&self, mut n: u64) -> u64 {
+        let mut v = 0;
+        while n > 0 {
+            n /= self.p;
+            v += n;
+        }
+        v
+    }
+
+    fn comb(&self, n: u64, k: u64) -> u64 {
+        let v = self.legendre(n) - self.legendre(k) - self.legendre(n - k);
+        if v >= self.e as u64 {
+            return 0;
+        }
+
+        let denom = self.fact_without_p(k) * self.fact_without_p(n - k) % self.q;
+        let inv = inv_mod(denom, self.q).unwrap();
+        self.fact_without_p(n) * inv % self.q * self.p.pow(v as u32) % self.q
+    }
+}
+
+impl CombMod {
+    pub fn new(m: u64) -> Self {
+        assert!(m > 0);
+        let mut parts = prime_factors(m as usize)
+            .into_iter()
+            .map(|(p, e)| PrimePower::new(p as u64, e as u32))
+            .collect::<Vec<_>>();
+        parts.sort_by_key(|part| part.p);
+        Self { m, parts }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.m
+    }
+
+    // Returns comb(n, k) mod m (0 when k > n)
+    pub fn comb(&self, n: u64, k: u64) -> u64 {
+        if k > n {
+            return 0;
+        }
+        let rm = self
+            .parts
+            .iter()
+            .map(|part| (part.comb(n, k), part.q))
+            .collect::<Vec<_>>();
+        garner(&rm, self.m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pascal(n: usize, m: u64) -> Vec<Vec<u64>> {
+        let mut c = vec![vec![1 % m]];
+        for _ in 0..n {
+            let prev = c.last().unwrap();
+            let mut row = vec![1 % m];
+            row.extend(prev.windows(2).map(|w| (w[0] + w[1]) % m));
+            row.push(1 % m);
+            c.push(row);
+        }
+        c
+    }
+
+    #[test]
+    fn test_small() {
+        for m in [1, 2, 4, 12, 36, 64, 97, 360, 1000, 1_741_824] {
+            let cm = CombMod::new(m);
+            let c = pascal(200, m);
+            for (n, row) in c.iter().enumerate() {
+                for (k, x) in row.iter().enumerate() {
+                    assert_eq!(cm.comb(n as u64, k as u64), *x);
+                }
+            }
+            assert_eq!(cm.comb(3, 5), 0);
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let cm = CombMod::new(1_000_000_000);
+        assert_eq!(cm.comb(12_345, 678), 233_188_480);
+        assert_eq!(cm.comb(100_000, 31_415), 337_600_000);
+
+        assert_eq!(CombMod::new(1 << 20).comb(12_345, 678), 846_464);
+        assert_eq!(CombMod::new(531_441).comb(100_000, 31_415), 26_730);
+
+        // same as Lucas' theorem for a prime modulus
+        let cm = CombMod::new(1009);
+        assert_eq!(cm.comb(987_654_321_987_654_321, 123_456_789), 547);
+    }
+}
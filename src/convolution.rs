@@ -0,0 +1,302 @@
+use crate::mint::StaticModInt;
+use crate::modular::const_primitive_root;
+
+struct NttInfo<const M: u32>;
+
+impl<const M: u32> NttInfo<M> {
+    const ROOT: u32 = const_primitive_root(M);
+}
+
+// Number theoretic transform in place
+// (a.len() should be a power of 2 which divides M - 1)
+pub fn ntt<const M: u32>(a: &mut [StaticModInt<M>]) {
+    butterfly(a, StaticModInt::new(NttInfo::<M>::ROOT));
+}
+
+// Inverse of ntt (including the division by a.len())
+pub fn intt<const M: u32>(a: &mut [StaticModInt<M>]) {
+    butterfly(a, StaticModInt::new(NttInfo::<M>::ROOT).inv());
+
+    let n_inv = StaticModInt::from(a.len()).inv();
+    for x in a.iter_mut() {
+        *x *= n_inv;
+    }
+}
+
+fn butterfly<const M: u32>(a: &mut [StaticModInt<M>], g: StaticModInt<M>) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    assert!(
+        (M - 1) as usize % n == 0,
+        "modulus is not NTT-friendly for this length"
+    );
+
+    // bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let w = g.pow((M - 1) / len as u32);
+        for i in (0..n).step_by(len) {
+            let mut wn = StaticModInt::new(1);
+            for j in 0..len / 2 {
+                let u = a[i + j];
+                let v = a[i + j + len / 2] * wn;
+                a[i + j] = u + v;
+                a[i + j + len / 2] = u - v;
+                wn *= w;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+fn convolution_naive<const M: u32>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+) -> Vec<StaticModInt<M>> {
+    let mut c = vec![StaticModInt::new(0); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            c[i + j] += *x * *y;
+        }
+    }
+    c
+}
+
+// Returns c such that c[k] = sum of a[i] * b[j] (i + j = k)
+// (M should be an NTT-friendly prime such as 998_244_353,
+//  where 2^k dividing M - 1 is at least a.len() + b.len() - 1)
+pub fn convolution<const M: u32>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+) -> Vec<StaticModInt<M>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    if std::cmp::min(a.len(), b.len()) <= 60 {
+        return convolution_naive(a, b);
+    }
+
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+
+    let mut fa = a.to_vec();
+    fa.resize(n, StaticModInt::new(0));
+    let mut fb = b.to_vec();
+    fb.resize(n, StaticModInt::new(0));
+
+    ntt(&mut fa);
+    ntt(&mut fb);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x *= *y;
+    }
+    intt(&mut fa);
+
+    fa.truncate(len);
+    fa
+}
+
+// NTT-friendly primes whose product (about 2^85.6) bounds the exact results
+const M1: u32 = 754_974_721;
+const M2: u32 = 167_772_161;
+const M3: u32 = 469_762_049;
+
+fn convolution_residues<const P: u32>(a: &[i128], b: &[i128]) -> Vec<StaticModInt<P>> {
+    let to_residues = |v: &[i128]| {
+        v.iter()
+            .map(|x| StaticModInt::from(x.rem_euclid(P as i128) as u64))
+            .collect::<Vec<_>>()
+    };
+    convolution(&to_residues(a), &to_residues(b))
+}
+
+// Returns c[k] modulo M1 * M2 * M3, recombined from three NTTs by Garner's algorithm
+fn convolution_three_primes(a: &[i128], b: &[i128]) -> Vec<u128> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let c1 = convolution_residues::<M1>(a, b);
+    let c2 = convolution_residues::<M2>(a, b);
+    let c3 = convolution_residues::<M3>(a, b);
+
+    let m1_inv = StaticModInt::<M2>::new(M1).inv();
+    let m1m2_inv = (StaticModInt::<M3>::new(M1) * StaticModInt::<M3>::new(M2)).inv();
+
+    c1.into_iter()
+        .zip(c2)
+        .zip(c3)
+        .map(|((r1, r2), r3)| {
+            // x = x1 + x2 * M1 + x3 * M1 * M2
+            let x1 = r1.value() as u64;
+            let x2 = ((r2 - StaticModInt::from(x1)) * m1_inv).value() as u64;
+            let x3 = ((r3 - StaticModInt::from(x1 + x2 * M1 as u64)) * m1m2_inv).value();
+            x1 as u128 + x2 as u128 * M1 as u128 + x3 as u128 * M1 as u128 * M2 as u128
+        })
+        .collect()
+}
+
+// convolution for any modulus (e.g. 1_000_000_007) by three NTTs and Garner's algorithm
+// (a.len() + b.len() - 1 should be at most 2^24)
+pub fn convolution_arbitrary_mod<const M: u32>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+) -> Vec<StaticModInt<M>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    if std::cmp::min(a.len(), b.len()) <= 60 {
+        return convolution_naive(a, b);
+    }
+
+    let to_i128 = |v: &[StaticModInt<M>]| v.iter().map(|x| x.value() as i128).collect::<Vec<_>>();
+    convolution_three_primes(&to_i128(a), &to_i128(b))
+        .into_iter()
+        .map(|x| StaticModInt::new((x % M as u128) as u32))
+        .collect()
+}
+
+// Exact convolution of integers
+// (every |c[k]| should be less than 2^84, and every c[k] should fit in i64)
+pub fn convolution_i64(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let to_i128 = |v: &[i64]| v.iter().map(|&x| x as i128).collect::<Vec<_>>();
+    let m = M1 as u128 * M2 as u128 * M3 as u128;
+    convolution_three_primes(&to_i128(a), &to_i128(b))
+        .into_iter()
+        .map(|x| if x > m / 2 { x as i128 - m as i128 } else { x as i128 } as i64)
+        .collect()
+}
+
+// Exact convolution of unsigned integers
+// (every c[k] should be less than 2^85)
+pub fn convolution_u128(a: &[u64], b: &[u64]) -> Vec<u128> {
+    let to_i128 = |v: &[u64]| v.iter().map(|&x| x as i128).collect::<Vec<_>>();
+    convolution_three_primes(&to_i128(a), &to_i128(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::Mint998244353;
+    use crate::test_util::sequence;
+
+    #[test]
+    fn test_ntt_root() {
+        assert_eq!(NttInfo::<998_244_353>::ROOT, 3);
+        assert_eq!(NttInfo::<754_974_721>::ROOT, 11);
+    }
+
+    #[test]
+    fn test_ntt_round_trip() {
+        for n in [1, 2, 4, 8, 256, 1024] {
+            let a = sequence::<998_244_353>(n, 3);
+            let mut b = a.clone();
+            ntt(&mut b);
+            intt(&mut b);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_convolution_small() {
+        let a = [1, 2, 3].map(Mint998244353::new);
+        let b = [4, 5].map(Mint998244353::new);
+        assert_eq!(convolution(&a, &b), [4, 13, 22, 15].map(Mint998244353::new));
+        assert!(convolution(&a, &[]).is_empty());
+        assert!(convolution::<998_244_353>(&[], &[]).is_empty());
+    }
+
+    fn check<const M: u32>() {
+        for (n, m) in [
+            (1, 1),
+            (61, 61),
+            (100, 1),
+            (100, 200),
+            (257, 511),
+            (1000, 1000),
+        ] {
+            let a = sequence::<M>(n, 1);
+            let b = sequence::<M>(m, 2);
+            assert_eq!(convolution(&a, &b), convolution_naive(&a, &b));
+        }
+    }
+
+    #[test]
+    fn test_convolution() {
+        check::<998_244_353>();
+        check::<167_772_161>();
+        check::<469_762_049>();
+        check::<754_974_721>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_not_ntt_friendly() {
+        let a = sequence::<1_000_000_007>(100, 1);
+        convolution(&a, &a);
+    }
+
+    #[test]
+    fn test_convolution_arbitrary_mod() {
+        for (n, m) in [(1, 1), (3, 100), (61, 61), (100, 200), (1000, 1000)] {
+            let a = sequence::<1_000_000_007>(n, 1);
+            let b = sequence::<1_000_000_007>(m, 2);
+            assert_eq!(convolution_arbitrary_mod(&a, &b), convolution_naive(&a, &b));
+        }
+
+        // worst case values
+        let a = vec![crate::mint::Mint::new(1_000_000_006); 1000];
+        assert_eq!(convolution_arbitrary_mod(&a, &a), convolution_naive(&a, &a));
+        assert!(convolution_arbitrary_mod::<1_000_000_007>(&[], &a).is_empty());
+    }
+
+    #[test]
+    fn test_convolution_i64() {
+        let a = (0..200i64)
+            .map(|i| (i * 7919 % 2001 - 1000) * 100_000)
+            .collect::<Vec<_>>();
+        let b = (0..300i64)
+            .map(|i| (i * 104_729 % 2001 - 1000) * 100_000)
+            .collect::<Vec<_>>();
+
+        let mut expected = vec![0i64; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                expected[i + j] += x * y;
+            }
+        }
+        assert_eq!(convolution_i64(&a, &b), expected);
+        assert_eq!(convolution_i64(&[-3], &[4, -5]), vec![-12, 15]);
+        assert!(convolution_i64(&[], &[1]).is_empty());
+    }
+
+    #[test]
+    fn test_convolution_u128() {
+        let a = (0..100u64)
+            .map(|i| (1 << 38) - i * 7919)
+            .collect::<Vec<_>>();
+        let b = (0..150u64)
+            .map(|i| (1 << 38) - i * 104_729)
+            .collect::<Vec<_>>();
+
+        let mut expected = vec![0u128; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                expected[i + j] += *x as u128 * *y as u128;
+            }
+        }
+        assert_eq!(convolution_u128(&a, &b), expected);
+    }
+}
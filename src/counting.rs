@@ -0,0 +1,314 @@
+use crate::fps::Fps;
+use crate::mint::StaticModInt;
+use crate::polynomial::interpolate_consecutive;
+use crate::precalc::Precalc;
+
+// Tables of Stirling numbers, Bell numbers, partition numbers and Bernoulli numbers
+// (*_table are simple O(n^2) DPs for small n, and the others are O(n log n) by FPS)
+
+// Returns c[i][j] = unsigned Stirling number of the first kind (0 <= j <= i <= n),
+// i.e. the number of permutations of i items with j cycles
+// (the signed one is (-1)^(i - j) c[i][j])
+pub fn stirling1_table<const M: u32>(n: usize) -> Vec<Vec<StaticModInt<M>>> {
+    let mut c = vec![vec![StaticModInt::new(1)]];
+    for i in 0..n {
+        // c[i + 1][j] = c[i][j - 1] + i * c[i][j]
+        let mut row = vec![StaticModInt::new(0); i + 2];
+        for (j, x) in c[i].iter().enumerate() {
+            row[j + 1] += *x;
+            row[j] += StaticModInt::from(i) * *x;
+        }
+        c.push(row);
+    }
+    c
+}
+
+// Returns s[i][j] = Stirling number of the second kind (0 <= j <= i <= n),
+// i.e. the number of ways to split i items into j non-empty groups
+pub fn stirling2_table<const M: u32>(n: usize) -> Vec<Vec<StaticModInt<M>>> {
+    let mut s = vec![vec![StaticModInt::new(1)]];
+    for i in 0..n {
+        // s[i + 1][j] = s[i][j - 1] + j * s[i][j]
+        let mut row = vec![StaticModInt::new(0); i + 2];
+        for (j, x) in s[i].iter().enumerate() {
+            row[j + 1] += *x;
+            row[j] += StaticModInt::from(j) * *x;
+        }
+        s.push(row);
+    }
+    s
+}
+
+// Returns Bell numbers B_0, ..., B_n by Bell triangle
+pub fn bell_table<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let mut bell = vec![StaticModInt::new(1)];
+    let mut row = vec![StaticModInt::new(1)];
+    for _ in 0..n {
+        // each row starts with the last element of the previous row
+        let mut next = vec![*row.last().unwrap()];
+        for x in row.iter() {
+            let last = *next.last().unwrap();
+            next.push(last + *x);
+        }
+        bell.push(next[0]);
+        row = next;
+    }
+    bell
+}
+
+// Returns partition numbers p(0), ..., p(n) by DP over the largest part
+pub fn partition_table<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let mut p = vec![StaticModInt::new(0); n + 1];
+    p[0] = StaticModInt::new(1);
+    for k in 1..=n {
+        for i in k..=n {
+            let x = p[i - k];
+            p[i] += x;
+        }
+    }
+    p
+}
+
+// Returns f(x + c) for f of degree d, by a convolution of f[i] i! and c^j / j!
+fn taylor_shift<const M: u32>(f: &Fps<M>, c: StaticModInt<M>, pc: &Precalc<M>) -> Fps<M> {
+    let d = f.len();
+    let a = Fps((0..d).rev().map(|i| f.0[i] * pc.fact(i)).collect());
+    let mut b = Fps(vec![StaticModInt::new(1); d]);
+    let mut cj = StaticModInt::new(1);
+    for (j, x) in b.0.iter_mut().enumerate() {
+        *x = cj * pc.ifact(j);
+        cj *= c;
+    }
+
+    let conv = (&a * &b).prefix(d);
+    Fps((0..d).map(|k| conv.0[d - 1 - k] * pc.ifact(k)).collect())
+}
+
+// Returns c(n, 0), ..., c(n, n) of the first kind in O(n log n),
+// as coefficients of x (x + 1) ... (x + n - 1)
+// (M should be a prime number larger than n)
+pub fn stirling1_row<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let pc = Precalc::new(n);
+
+    // f = x (x + 1) ... (x + m - 1), and f(x) f(x + m) doubles m
+    let mut f = Fps(vec![StaticModInt::new(1)]);
+    let mut m = 0;
+    for bit in (0..usize::BITS - n.leading_zeros()).rev() {
+        if m > 0 {
+            f = &f * &taylor_shift(&f, StaticModInt::from(m), &pc);
+            m *= 2;
+        }
+        if n >> bit & 1 == 1 {
+            f = &f * &Fps(vec![StaticModInt::from(m), StaticModInt::new(1)]);
+            m += 1;
+        }
+    }
+    f.prefix(n + 1).0
+}
+
+// Returns S(n, 0), ..., S(n, n) of the second kind in O(n log n)
+// by S(n, k) = sum of (-1)^(k - j) j^n / (j! (k - j)!)
+// (M should be a prime number larger than n)
+pub fn stirling2_row<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let pc = Precalc::new(n);
+    let a = Fps((0..=n)
+        .map(|j| StaticModInt::from(j).pow(n as u32) * pc.ifact(j))
+        .collect());
+    let b = Fps((0..=n)
+        .map(|i| {
+            if i % 2 == 0 {
+                pc.ifact(i)
+            } else {
+                -pc.ifact(i)
+            }
+        })
+        .collect());
+    (&a * &b).prefix(n + 1).0
+}
+
+// Returns Bell numbers B_0, ..., B_n in O(n log n) as n! [x^n] exp(e^x - 1)
+// (M should be a prime number larger than n)
+pub fn bell_numbers<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let pc = Precalc::new(n);
+    let mut f = Fps((0..=n).map(|i| pc.ifact(i)).collect());
+    f.0[0] = StaticModInt::new(0);
+    let g = f.exp(n + 1);
+    (0..=n).map(|i| g.0[i] * pc.fact(i)).collect()
+}
+
+// Returns partition numbers p(0), ..., p(n) in O(n log n)
+// as the inverse of prod (1 - x^k) = sum of (-1)^k x^(k (3k - 1) / 2) (pentagonal number theorem)
+// (M should be a prime number)
+pub fn partition_numbers<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let mut q = Fps::zeros(n + 1);
+    q.0[0] = StaticModInt::new(1);
+    for k in 1.. {
+        let sign = if k % 2 == 0 { 1 } else { M - 1 };
+        let (d1, d2) = (k * (3 * k - 1) / 2, k * (3 * k + 1) / 2);
+        if d1 > n {
+            break;
+        }
+        q.0[d1] += StaticModInt::new(sign);
+        if d2 <= n {
+            q.0[d2] += StaticModInt::new(sign);
+        }
+    }
+    q.inv(n + 1).0
+}
+
+// Returns Bernoulli numbers B_0, ..., B_n (B_1 = -1/2) in O(n log n)
+// as n! [x^n] x / (e^x - 1)
+// (M should be a prime number larger than n + 1)
+pub fn bernoulli<const M: u32>(n: usize) -> Vec<StaticModInt<M>> {
+    let pc = Precalc::new(n + 1);
+    let f = Fps((0..=n).map(|i| pc.ifact(i + 1)).collect());
+    let g = f.inv(n + 1);
+    (0..=n).map(|i| g.0[i] * pc.fact(i)).collect()
+}
+
+// Returns 1^k + 2^k + ... + n^k in O(k log k)
+// by interpolating the polynomial of degree k + 1 in n from its values at 0, ..., k + 1
+// (M should be a prime number larger than k + 1)
+pub fn power_sum<const M: u32>(n: u64, k: usize) -> StaticModInt<M> {
+    let pc = Precalc::new(k + 1);
+    let mut ys = vec![StaticModInt::new(0); k + 2];
+    for i in 1..k + 2 {
+        ys[i] = ys[i - 1] + StaticModInt::from(i).pow(k as u32);
+    }
+    interpolate_consecutive(&ys, StaticModInt::from(n), &pc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::{Mint, Mint998244353};
+
+    fn to_u32<const M: u32>(v: &[StaticModInt<M>]) -> Vec<u32> {
+        v.iter().map(|x| x.value()).collect()
+    }
+
+    #[test]
+    fn test_small_values() {
+        let c = stirling1_table::<998_244_353>(5);
+        assert_eq!(to_u32(&c[0]), [1]);
+        assert_eq!(to_u32(&c[1]), [0, 1]);
+        assert_eq!(to_u32(&c[5]), [0, 24, 50, 35, 10, 1]);
+
+        let s = stirling2_table::<998_244_353>(5);
+        assert_eq!(to_u32(&s[0]), [1]);
+        assert_eq!(to_u32(&s[5]), [0, 1, 15, 25, 10, 1]);
+
+        assert_eq!(
+            to_u32(&bell_table::<998_244_353>(9)),
+            [1, 1, 2, 5, 15, 52, 203, 877, 4140, 21_147]
+        );
+        assert_eq!(
+            to_u32(&partition_table::<998_244_353>(10)),
+            [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42]
+        );
+    }
+
+    fn check<const M: u32>() {
+        let n = 200;
+        let c = stirling1_table::<M>(n);
+        let s = stirling2_table::<M>(n);
+        for i in [0, 1, 2, 3, 7, 64, 100, 200] {
+            assert_eq!(stirling1_row::<M>(i), c[i]);
+            assert_eq!(stirling2_row::<M>(i), s[i]);
+        }
+
+        let bell = bell_table::<M>(n);
+        assert_eq!(bell_numbers::<M>(n), bell);
+        for (i, b) in bell.iter().enumerate() {
+            assert_eq!(s[i].iter().copied().sum::<StaticModInt<M>>(), *b);
+        }
+        assert_eq!(partition_numbers::<M>(n), partition_table::<M>(n));
+        assert_eq!(partition_numbers::<M>(0), vec![StaticModInt::new(1)]);
+    }
+
+    #[test]
+    fn test_tables_and_rows() {
+        check::<998_244_353>();
+        check::<1_000_000_007>();
+    }
+
+    #[test]
+    fn test_large() {
+        assert_eq!(partition_numbers::<998_244_353>(100)[100], 190_569_292);
+        assert_eq!(partition_numbers::<1_000_000_007>(1000)[1000], 709_496_666);
+        assert_eq!(partition_numbers::<998_244_353>(1000)[1000], 627_356_119);
+
+        assert_eq!(bell_numbers::<1_000_000_007>(1000)[1000], 465_231_251);
+        assert_eq!(bell_numbers::<998_244_353>(1000)[1000], 574_216_159);
+
+        assert_eq!(
+            stirling1_row::<1_000_000_007>(1000)[500],
+            Mint::new(761_367_694)
+        );
+        assert_eq!(
+            stirling2_row::<1_000_000_007>(1000)[500],
+            Mint::new(596_728_287)
+        );
+        assert_eq!(
+            stirling1_row::<998_244_353>(1000)[500],
+            Mint998244353::new(917_091_654)
+        );
+        assert_eq!(
+            stirling2_row::<998_244_353>(1000)[500],
+            Mint998244353::new(101_471_401)
+        );
+    }
+
+    #[test]
+    fn test_bernoulli() {
+        let b = bernoulli::<1_000_000_007>(10);
+        let expected = [
+            (1, 1),
+            (-1, 2),
+            (1, 6),
+            (0, 1),
+            (-1, 30),
+            (0, 1),
+            (1, 42),
+            (0, 1),
+            (-1, 30),
+            (0, 1),
+            (5, 66),
+        ];
+        for (x, (p, q)) in b.iter().zip(expected) {
+            assert_eq!(*x, Mint::from(p as i64) / Mint::new(q));
+        }
+        assert_eq!(bernoulli::<1_000_000_007>(100)[100], 94_103_270);
+        assert_eq!(bernoulli::<998_244_353>(0), vec![Mint998244353::new(1)]);
+    }
+
+    #[test]
+    fn test_power_sum() {
+        for k in 0..10 {
+            let mut sum = Mint::new(0);
+            for n in 0..50u32 {
+                assert_eq!(power_sum::<1_000_000_007>(n as u64, k), sum);
+                sum += Mint::new(n + 1).pow(k as u32);
+            }
+        }
+
+        let n = 1_000_000_000_000_000_000;
+        assert_eq!(power_sum::<1_000_000_007>(n, 0), Mint::from(n));
+        assert_eq!(power_sum::<1_000_000_007>(n, 1), 1225);
+        assert_eq!(power_sum::<1_000_000_007>(n, 2), 40_425);
+        assert_eq!(power_sum::<1_000_000_007>(n, 3), 1_500_625);
+
+        // Faulhaber's formula: (k + 1) * sum = sum of comb(k + 1, j) B_j (n + 1)^(k + 1 - j) (k > 0)
+        let k = 300;
+        let b = bernoulli::<998_244_353>(k);
+        let pc = Precalc::<998_244_353>::new(k + 1);
+        let x = Mint998244353::from(n + 1);
+        let faulhaber = (0..=k)
+            .map(|j| pc.comb(k + 1, j) * b[j] * x.pow((k + 1 - j) as u32))
+            .sum::<Mint998244353>();
+        assert_eq!(
+            power_sum::<998_244_353>(n, k) * Mint998244353::from(k + 1),
+            faulhaber
+        );
+    }
+}
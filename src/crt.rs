@@ -0,0 +1,186 @@
+use crate::mint::inv_mod;
+use crate::prime::prime_factors;
+use num_integer::gcd;
+
+// Returns (r, lcm) such that x = r (mod lcm) is equivalent to x = r_i (mod m_i) for every i,
+// or None when there is no such x or the lcm overflows i64 (moduli need not be coprime)
+// (m_i should be positive)
+pub fn crt(rm: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut r0, mut m0) = (0i128, 1i128);
+    for &(r1, m1) in rm {
+        assert!(m1 > 0);
+        let (mut r1, mut m1) = ((r1 as i128).rem_euclid(m1 as i128), m1 as i128);
+        if m0 < m1 {
+            std::mem::swap(&mut r0, &mut r1);
+            std::mem::swap(&mut m0, &mut m1);
+        }
+
+        if m0 % m1 == 0 {
+            if r0 % m1 != r1 {
+                return None;
+            }
+            continue;
+        }
+
+        // x = r0 + m0 * t, where m0 * t = r1 - r0 (mod m1)
+        let g = gcd(m0, m1);
+        if (r1 - r0) % g != 0 {
+            return None;
+        }
+        let u1 = m1 / g;
+        let inv = inv_mod((m0 / g % u1) as u64, u1 as u64).unwrap() as i128;
+        let t = ((r1 - r0) / g).rem_euclid(u1) * inv % u1;
+
+        r0 += t * m0;
+        m0 *= u1;
+        if m0 > i64::MAX as i128 {
+            return None;
+        }
+    }
+    Some((r0 as i64, m0 as i64))
+}
+
+// Returns x mod target, where x is the unique value in [0, m_0 * m_1 * ...)
+// such that x = r_i (mod m_i), by Garner's algorithm in O(n^2)
+// (moduli should be pairwise coprime, see to_coprime otherwise)
+pub fn garner(rm: &[(u64, u64)], target: u64) -> u64 {
+    assert!(target > 0);
+    let n = rm.len();
+
+    // x = t_0 + t_1 * m_0 + t_2 * m_0 * m_1 + ...,
+    // and coeffs[j] = m_0 * ... * m_(i - 1), constants[j] = t_0 + ... (mod m_j or target)
+    let mods = rm
+        .iter()
+        .map(|&(_, m)| m)
+        .chain(std::iter::once(target))
+        .collect::<Vec<_>>();
+    let mut coeffs = mods.iter().map(|&m| 1 % m).collect::<Vec<_>>();
+    let mut constants = vec![0u64; n + 1];
+    for (i, &(r, m)) in rm.iter().enumerate() {
+        let inv = inv_mod(coeffs[i], m).expect("moduli are not pairwise coprime");
+        let diff = ((r % m) as u128 + m as u128 - constants[i] as u128) % m as u128;
+        let t = diff * inv as u128 % m as u128;
+        for j in i + 1..=n {
+            let mj = mods[j] as u128;
+            constants[j] = ((constants[j] as u128 + t * coeffs[j] as u128) % mj) as u64;
+            coeffs[j] = (coeffs[j] as u128 * m as u128 % mj) as u64;
+        }
+    }
+    constants[n]
+}
+
+// Splits every modulus into prime powers and keeps the largest power of each prime,
+// so that the result can be passed to garner, or None when the congruences conflict
+// (moduli are factorized by prime::prime_factors)
+pub fn to_coprime(rm: &[(u64, u64)]) -> Option<Vec<(u64, u64)>> {
+    // prime -> (residue, prime power)
+    let mut table = std::collections::HashMap::<u64, (u64, u64)>::new();
+    for &(r, m) in rm {
+        for (p, k) in prime_factors(m as usize) {
+            let pk = (p as u64).pow(k as u32);
+            let r = r % pk;
+            let entry = table.entry(p as u64).or_insert((r, pk));
+            let (small, large) = if entry.1 <= pk {
+                (*entry, (r, pk))
+            } else {
+                ((r, pk), *entry)
+            };
+            if large.0 % small.1 != small.0 {
+                return None;
+            }
+            *entry = large;
+        }
+    }
+
+    let mut ret = table.into_values().collect::<Vec<_>>();
+    ret.sort_by_key(|&(_, m)| m);
+    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force_crt(rm: &[(i64, i64)]) -> Option<(i64, i64)> {
+        let lcm = rm
+            .iter()
+            .fold(1, |l, &(_, m)| l / gcd(l as i128, m as i128) as i64 * m);
+        (0..lcm)
+            .find(|x| rm.iter().all(|&(r, m)| (x - r).rem_euclid(m) == 0))
+            .map(|x| (x, lcm))
+    }
+
+    #[test]
+    fn test_crt_small() {
+        for m1 in 1..=12 {
+            for m2 in 1..=12 {
+                for r1 in -3..m1 {
+                    for r2 in 0..m2 {
+                        let rm = [(r1, m1), (r2, m2)];
+                        assert_eq!(crt(&rm), brute_force_crt(&rm));
+                    }
+                }
+            }
+        }
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn test_crt_large() {
+        let m1 = 1_000_000_007;
+        let m2 = 998_244_353;
+        let x = 123_456_789_012_345_678 % (m1 * m2);
+        assert_eq!(crt(&[(x % m1, m1), (x % m2, m2)]), Some((x, m1 * m2)));
+
+        // lcm close to i64::MAX with a large common factor
+        let g = 1 << 30;
+        let (m1, m2) = (g * 3, g * 2_000_000_011);
+        let (x, lcm) = (9_000_000_000_000_000_000, g * 6_000_000_033);
+        assert_eq!(crt(&[(x % m1, m1), (x % m2, m2)]), Some((x % lcm, lcm)));
+
+        // lcm overflows i64
+        let (m1, m2) = (i64::MAX, i64::MAX - 1);
+        assert_eq!(crt(&[(0, m1), (0, m2)]), None);
+        assert_eq!(crt(&[(1, 1 << 62), (1, 3)]), None);
+    }
+
+    #[test]
+    fn test_garner() {
+        let rm = [(2, 3), (3, 5), (2, 7)];
+        assert_eq!(garner(&rm, 1_000_000_007), 23);
+        assert_eq!(garner(&rm, 10), 3);
+        assert_eq!(garner(&[], 7), 0);
+
+        // x = 2^100 mod the product of three primes, reduced by other targets
+        let x = 1u128 << 100;
+        let ms = [1_000_000_007u64, 998_244_353, 1_000_000_009];
+        let prod = ms.iter().map(|&m| m as u128).product::<u128>();
+        let rm = ms.map(|m| ((x % m as u128) as u64, m));
+        for target in [1, 2, 1_000_000_007, u64::MAX] {
+            assert_eq!(garner(&rm, target) as u128, x % prod % target as u128);
+        }
+
+        // moduli larger than 2^63
+        let (m1, m2) = (u64::MAX, u64::MAX - 1);
+        let x = (1u128 << 127) + 12345;
+        let rm = [((x % m1 as u128) as u64, m1), ((x % m2 as u128) as u64, m2)];
+        assert_eq!(
+            garner(&rm, u64::MAX - 2) as u128,
+            x % (u64::MAX - 2) as u128
+        );
+    }
+
+    #[test]
+    fn test_to_coprime() {
+        let rm = [(5, 12), (11, 18)];
+        let coprime = to_coprime(&rm).unwrap();
+        assert_eq!(coprime, vec![(1, 4), (2, 9)]);
+        assert_eq!(garner(&coprime, 1_000_000_007), 29);
+        assert_eq!(crt(&[(5, 12), (11, 18)]), Some((29, 36)));
+
+        assert_eq!(to_coprime(&[(1, 4), (2, 6)]), None);
+        assert_eq!(to_coprime(&[(0, 1)]), Some(vec![]));
+    }
+}
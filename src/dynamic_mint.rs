@@ -0,0 +1,358 @@
+use crate::mint::{inv_mod, parse_mod, ParseModIntError};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+// Barrett reduction for a modulus given at runtime
+// (m * m fits in u64, so a product is reduced with two multiplications instead of `%`)
+pub struct Barrett {
+    m: AtomicU32,
+    im: AtomicU64,
+}
+
+impl Barrett {
+    pub const fn new(m: u32) -> Self {
+        Self {
+            m: AtomicU32::new(m),
+            im: AtomicU64::new((u64::MAX / m as u64).wrapping_add(1)),
+        }
+    }
+
+    pub fn set(&self, m: u32) {
+        self.m.store(m, Ordering::Relaxed);
+        self.im
+            .store((u64::MAX / m as u64).wrapping_add(1), Ordering::Relaxed);
+    }
+
+    pub fn umod(&self) -> u32 {
+        self.m.load(Ordering::Relaxed)
+    }
+
+    // Returns a * b % m (a and b should be less than m)
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        let m = self.umod();
+        let im = self.im.load(Ordering::Relaxed);
+
+        let z = a as u64 * b as u64;
+        let x = ((z as u128 * im as u128) >> 64) as u64;
+        let mut v = z.wrapping_sub(x.wrapping_mul(m as u64)) as u32;
+        if m <= v {
+            v = v.wrapping_add(m);
+        }
+        v
+    }
+}
+
+// Tag type which owns the modulus of DynamicModInt<Self>
+pub trait ModulusId: 'static {
+    fn barrett() -> &'static Barrett;
+}
+
+// Defines a new tag type for DynamicModInt
+// (its modulus is 1_000_000_007 until set_modulus is called)
+//
+// examples:
+//   modulus_id!(MyId);
+//   DynamicModInt::<MyId>::set_modulus(998_244_353);
+//
+#[macro_export]
+macro_rules! modulus_id {
+    ($name:ident) => {
+        pub enum $name {}
+
+        impl $crate::dynamic_mint::ModulusId for $name {
+            fn barrett() -> &'static $crate::dynamic_mint::Barrett {
+                static BARRETT: $crate::dynamic_mint::Barrett =
+                    $crate::dynamic_mint::Barrett::new(1_000_000_007);
+                &BARRETT
+            }
+        }
+    };
+}
+
+modulus_id!(DefaultId);
+
+pub type DynamicMint = DynamicModInt<DefaultId>;
+
+// Modular integer whose modulus is chosen at runtime
+// (the modulus is shared by all values with the same tag type I)
+pub struct DynamicModInt<I: ModulusId> {
+    value: u32,
+    phantom: std::marker::PhantomData<fn() -> I>,
+}
+
+impl<I: ModulusId> Clone for DynamicModInt<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: ModulusId> Copy for DynamicModInt<I> {}
+
+impl<I: ModulusId> std::fmt::Debug for DynamicModInt<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DynamicModInt")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<I: ModulusId> std::ops::Add for DynamicModInt<I> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut d = self.value + rhs.value;
+        if d >= Self::modulus() {
+            d -= Self::modulus();
+        }
+        Self::raw(d)
+    }
+}
+
+impl<I: ModulusId> std::ops::AddAssign for DynamicModInt<I> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<I: ModulusId> std::ops::Sub for DynamicModInt<I> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut d = self.value + Self::modulus() - rhs.value;
+        if d >= Self::modulus() {
+            d -= Self::modulus();
+        }
+        Self::raw(d)
+    }
+}
+
+impl<I: ModulusId> std::ops::SubAssign for DynamicModInt<I> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<I: ModulusId> std::ops::Mul for DynamicModInt<I> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::raw(I::barrett().mul(self.value, rhs.value))
+    }
+}
+
+impl<I: ModulusId> std::ops::MulAssign for DynamicModInt<I> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<I: ModulusId> std::ops::Neg for DynamicModInt<I> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let d = match self.value {
+            0 => 0,
+            _ => Self::modulus() - self.value,
+        };
+
+        Self::raw(d)
+    }
+}
+
+impl<I: ModulusId> std::fmt::Display for DynamicModInt<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<I: ModulusId> From<usize> for DynamicModInt<I> {
+    fn from(val: usize) -> Self {
+        let d = (val % Self::modulus() as usize) as u32;
+
+        Self::raw(d)
+    }
+}
+
+// parsed with the modulus at the time of reading
+impl<I: ModulusId> std::str::FromStr for DynamicModInt<I> {
+    type Err = ParseModIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_mod(s, Self::modulus() as u64).map(|d| Self::raw(d as u32))
+    }
+}
+
+impl<I: ModulusId> PartialEq<Self> for DynamicModInt<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<I: ModulusId> PartialEq<u32> for DynamicModInt<I> {
+    fn eq(&self, other: &u32) -> bool {
+        self.value == *other
+    }
+}
+
+#[allow(dead_code)]
+impl<I: ModulusId> DynamicModInt<I> {
+    // Changes the modulus of every value tagged by I
+    // (values created before this call are no longer meaningful)
+    pub fn set_modulus(m: u32) {
+        assert!((1..1 << 31).contains(&m));
+        I::barrett().set(m);
+    }
+
+    pub fn modulus() -> u32 {
+        I::barrett().umod()
+    }
+
+    pub fn new(n: u32) -> Self {
+        Self::raw(n % Self::modulus())
+    }
+
+    fn raw(value: u32) -> Self {
+        Self {
+            value,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    pub fn pow(self, mut n: u32) -> Self {
+        let mut t = Self::new(1);
+        let mut s = self;
+        while n > 0 {
+            if n & 1 == 1 {
+                t *= s;
+            }
+            s *= s;
+            n >>= 1;
+        }
+        t
+    }
+
+    // Returns the inverse, or None when gcd(value, modulus) != 1
+    pub fn checked_inv(self) -> Option<Self> {
+        inv_mod(self.value as u64, Self::modulus() as u64).map(|x| Self::raw(x as u32))
+    }
+
+    pub fn inv(self) -> Self {
+        self.checked_inv()
+            .expect("value is not invertible under the modulus")
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_barrett() {
+        let b = Barrett::new(1_000_000_007);
+        assert_eq!(b.mul(0, 5), 0);
+        assert_eq!(b.mul(1_000_000_006, 1_000_000_006), 1);
+        assert_eq!(b.mul(123_456_789, 987_654_321), 259_106_859);
+
+        b.set(2_147_483_647);
+        assert_eq!(b.umod(), 2_147_483_647);
+        assert_eq!(b.mul(2_147_483_646, 2_147_483_646), 1);
+
+        b.set(1);
+        assert_eq!(b.mul(0, 0), 0);
+    }
+
+    #[test]
+    fn test_default_modulus() {
+        assert_eq!(DynamicMint::modulus(), 1_000_000_007);
+        assert_eq!(DynamicMint::new(1_000_000_008), 1);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        modulus_id!(Id);
+        type Dm = DynamicModInt<Id>;
+        Dm::set_modulus(998_244_353);
+
+        assert_eq!(Dm::new(998_244_350) + Dm::new(5), 2);
+        assert_eq!(Dm::new(3) - Dm::new(5), 998_244_351);
+        assert_eq!(Dm::new(998_244_352) * Dm::new(998_244_352), 1);
+        assert_eq!(-Dm::new(0), 0);
+        assert_eq!(-Dm::new(1), 998_244_352);
+
+        let mut m = Dm::new(10);
+        m += Dm::new(5);
+        m -= Dm::new(3);
+        m *= Dm::new(2);
+        assert_eq!(m, Dm::new(24));
+    }
+
+    #[test]
+    fn test_pow_inv() {
+        modulus_id!(Id);
+        type Dm = DynamicModInt<Id>;
+        Dm::set_modulus(13);
+
+        assert_eq!(Dm::new(2).pow(12), 1);
+        assert_eq!(Dm::new(2).pow(5), 6);
+        for i in 1..13 {
+            assert_eq!(Dm::new(i) * Dm::new(i).inv(), 1);
+        }
+    }
+
+    #[test]
+    fn test_checked_inv() {
+        modulus_id!(Id);
+        type Dm = DynamicModInt<Id>;
+        Dm::set_modulus(12);
+
+        assert_eq!(Dm::new(0).checked_inv(), None);
+        assert_eq!(Dm::new(4).checked_inv(), None);
+        assert_eq!(Dm::new(5).checked_inv(), Some(Dm::new(5)));
+        assert_eq!(Dm::new(7).inv(), 7);
+        assert_eq!(Dm::new(11).inv(), 11);
+    }
+
+    #[test]
+    fn test_from_usize_and_format() {
+        modulus_id!(Id);
+        type Dm = DynamicModInt<Id>;
+        Dm::set_modulus(7);
+
+        assert_eq!(Dm::from(100).value(), 2);
+        assert_eq!(format!("{}", Dm::new(20)), "6");
+        assert_eq!(format!("{:?}", Dm::new(20)), "DynamicModInt { value: 6 }");
+    }
+
+    #[test]
+    fn test_same_as_mint() {
+        use crate::mint::Mint;
+
+        modulus_id!(Id);
+        type Dm = DynamicModInt<Id>;
+        Dm::set_modulus(Mint::MODULUS);
+
+        let mut m = Mint::new(1);
+        let mut d = Dm::new(1);
+        for i in 1..1000 {
+            m = m * Mint::new(i * 7919) + Mint::new(i);
+            d = d * Dm::new(i * 7919) + Dm::new(i);
+            assert_eq!(m.value(), d.value());
+        }
+        assert_eq!(m.inv().value(), d.inv().value());
+    }
+
+    #[test]
+    fn test_from_str() {
+        modulus_id!(Id);
+        type Dm = DynamicModInt<Id>;
+        Dm::set_modulus(7);
+
+        let source = proconio::source::once::OnceSource::from("3 -1 100");
+        proconio::input! {
+            from source,
+            a: [Dm; 3],
+        }
+        assert_eq!(a, [3, 6, 2].map(Dm::new));
+        assert!("".parse::<Dm>().is_err());
+    }
+}
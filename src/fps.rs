@@ -0,0 +1,512 @@
+use crate::convolution::{convolution, convolution_arbitrary_mod};
+use crate::mint::{StaticModInt, DEFAULT_MODULUS};
+
+// Formal power series (coefficients are stored from the lowest degree)
+// (modulus should be a prime number, products use NTT when M is NTT-friendly)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fps<const M: u32 = DEFAULT_MODULUS>(pub Vec<StaticModInt<M>>);
+
+impl<const M: u32> From<Vec<StaticModInt<M>>> for Fps<M> {
+    fn from(coeffs: Vec<StaticModInt<M>>) -> Self {
+        Self(coeffs)
+    }
+}
+
+impl<const M: u32> std::ops::Add for &Fps<M> {
+    type Output = Fps<M>;
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut c = self.clone();
+        c += rhs;
+        c
+    }
+}
+
+impl<const M: u32> std::ops::Add for Fps<M> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+impl<const M: u32> std::ops::AddAssign<&Self> for Fps<M> {
+    fn add_assign(&mut self, rhs: &Self) {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), StaticModInt::new(0));
+        }
+        for (x, y) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *x += *y;
+        }
+    }
+}
+
+impl<const M: u32> std::ops::Sub for &Fps<M> {
+    type Output = Fps<M>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut c = self.clone();
+        c -= rhs;
+        c
+    }
+}
+
+impl<const M: u32> std::ops::Sub for Fps<M> {
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= &rhs;
+        self
+    }
+}
+
+impl<const M: u32> std::ops::SubAssign<&Self> for Fps<M> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), StaticModInt::new(0));
+        }
+        for (x, y) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *x -= *y;
+        }
+    }
+}
+
+impl<const M: u32> std::ops::Mul for &Fps<M> {
+    type Output = Fps<M>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let len = self.0.len() + rhs.0.len();
+        if len > 0 && (M - 1) as usize % (len - 1).next_power_of_two() == 0 {
+            Fps(convolution(&self.0, &rhs.0))
+        } else {
+            Fps(convolution_arbitrary_mod(&self.0, &rhs.0))
+        }
+    }
+}
+
+impl<const M: u32> std::ops::Mul for Fps<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<const M: u32> std::ops::MulAssign<&Self> for Fps<M> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<const M: u32> std::ops::Mul<StaticModInt<M>> for Fps<M> {
+    type Output = Self;
+    fn mul(mut self, rhs: StaticModInt<M>) -> Self::Output {
+        for x in self.0.iter_mut() {
+            *x *= rhs;
+        }
+        self
+    }
+}
+
+impl<const M: u32> std::ops::Neg for Fps<M> {
+    type Output = Self;
+    fn neg(mut self) -> Self::Output {
+        for x in self.0.iter_mut() {
+            *x = -*x;
+        }
+        self
+    }
+}
+
+#[allow(dead_code)]
+impl<const M: u32> Fps<M> {
+    pub fn new(coeffs: Vec<StaticModInt<M>>) -> Self {
+        Self(coeffs)
+    }
+
+    pub fn zeros(n: usize) -> Self {
+        Self(vec![StaticModInt::new(0); n])
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Drops the terms whose degree is n or more
+    pub fn truncate(&mut self, n: usize) {
+        self.0.truncate(n);
+    }
+
+    // Returns self mod x^n with exactly n coefficients (padded with zeros)
+    pub fn prefix(&self, n: usize) -> Self {
+        let mut c = self.0[..std::cmp::min(n, self.0.len())].to_vec();
+        c.resize(n, StaticModInt::new(0));
+        Self(c)
+    }
+
+    pub fn derivative(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, x)| *x * StaticModInt::from(i))
+                .collect(),
+        )
+    }
+
+    // Returns the integral whose constant term is 0
+    pub fn integral(&self) -> Self {
+        let inv = Self::inverses(self.0.len());
+        let mut c = vec![StaticModInt::new(0)];
+        c.extend(self.0.iter().zip(inv.iter().skip(1)).map(|(x, y)| *x * *y));
+        Self(c)
+    }
+
+    // Returns 1 / 1, ..., 1 / n (index 0 is unused)
+    fn inverses(n: usize) -> Vec<StaticModInt<M>> {
+        let mut inv = vec![StaticModInt::new(1); n + 1];
+        for i in 2..=n {
+            inv[i] = -inv[M as usize % i] * StaticModInt::from(M as usize / i);
+        }
+        inv
+    }
+
+    // Returns f(x^k)
+    pub fn compose_x_pow(&self, k: usize) -> Self {
+        assert!(k > 0);
+        if self.0.is_empty() {
+            return Self(vec![]);
+        }
+
+        let mut c = Self::zeros((self.0.len() - 1) * k + 1);
+        for (i, x) in self.0.iter().enumerate() {
+            c.0[i * k] = *x;
+        }
+        c
+    }
+
+    // Returns 1 / self mod x^n by Newton's method
+    // (constant term should not be 0)
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(!self.0.is_empty() && self.0[0] != 0);
+
+        let mut g = Self(vec![self.0[0].inv()]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            // g = g * (2 - f * g)
+            let mut t = -(&self.prefix(m) * &g).prefix(m);
+            t.0[0] += StaticModInt::new(2);
+            g = (&g * &t).prefix(m);
+        }
+        g.prefix(n)
+    }
+
+    // Returns log(self) mod x^n
+    // (constant term should be 1)
+    pub fn log(&self, n: usize) -> Self {
+        assert!(!self.0.is_empty() && self.0[0] == 1);
+        if n == 0 {
+            return Self(vec![]);
+        }
+
+        (&self.derivative() * &self.inv(n)).prefix(n - 1).integral()
+    }
+
+    // Returns exp(self) mod x^n by Newton's method
+    // (constant term should be 0)
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.0.is_empty() || self.0[0] == 0);
+
+        let mut g = Self(vec![StaticModInt::new(1)]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            // g = g * (1 - log(g) + f)
+            let mut t = &self.prefix(m) - &g.log(m);
+            t.0[0] += StaticModInt::new(1);
+            g = (&g * &t).prefix(m);
+        }
+        g.prefix(n)
+    }
+
+    // Returns self^k mod x^n
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            let mut c = Self::zeros(n);
+            if n > 0 {
+                c.0[0] = StaticModInt::new(1);
+            }
+            return c;
+        }
+
+        let l = match self.0.iter().position(|x| *x != 0) {
+            Some(l) => l,
+            None => return Self::zeros(n),
+        };
+        if l as u128 * k as u128 >= n as u128 {
+            return Self::zeros(n);
+        }
+
+        // self = c * x^l * g (constant term of g is 1)
+        let shift = l * k as usize;
+        let c = self.0[l];
+        let g = Self(self.0[l..].to_vec()) * c.inv();
+        let h = (g.log(n - shift) * StaticModInt::from(k)).exp(n - shift);
+
+        let mut coeffs = vec![StaticModInt::new(0); shift];
+        coeffs.extend((h * c.pow((k % (M as u64 - 1)) as u32)).0);
+        Self(coeffs)
+    }
+
+    // Returns g such that g * g = self mod x^n
+    // (None when no such g exists)
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let l = match self.0.iter().position(|x| *x != 0) {
+            Some(l) => l,
+            None => return Some(Self::zeros(n)),
+        };
+        // self = 0 mod x^n
+        if l >= n {
+            return Some(Self::zeros(n));
+        }
+        if l % 2 == 1 {
+            return None;
+        }
+        let shift = l / 2;
+
+        // self = c * x^l * g (constant term of g is 1)
+        let c = self.0[l];
+        let r = c.sqrt()?;
+        let g = Self(self.0[l..].to_vec()) * c.inv();
+        let h = (g.log(n - shift) * StaticModInt::new(2).inv()).exp(n - shift);
+
+        let mut coeffs = vec![StaticModInt::new(0); shift];
+        coeffs.extend((h * r).0);
+        Some(Self(coeffs))
+    }
+
+    // Returns self(x) by Horner's method
+    pub fn evaluate(&self, x: StaticModInt<M>) -> StaticModInt<M> {
+        self.0
+            .iter()
+            .rev()
+            .fold(StaticModInt::new(0), |acc, c| acc * x + *c)
+    }
+
+    // Returns (q, r) such that self = q * rhs + r and deg(r) < deg(rhs) as polynomials
+    // (r has exactly deg(rhs) coefficients)
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let m = match rhs.0.iter().rposition(|x| *x != 0) {
+            Some(d) => d + 1,
+            None => panic!("division by zero polynomial"),
+        };
+        let n = self.0.len();
+        if n < m {
+            return (Self(vec![]), self.prefix(m - 1));
+        }
+
+        // reversed coefficients turn polynomial division into power series division
+        let k = n - m + 1;
+        let rev_f = Self(self.0.iter().rev().take(k).copied().collect());
+        let rev_g = Self(rhs.0[..m].iter().rev().copied().collect());
+        let mut q = (&rev_f * &rev_g.inv(k)).prefix(k);
+        q.0.reverse();
+
+        let r = (self - &(&q * rhs)).prefix(m - 1);
+        (q, r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::Mint;
+    use crate::test_util::sequence;
+
+    fn fps<const M: u32>(v: &[u32]) -> Fps<M> {
+        Fps(v.iter().map(|&x| StaticModInt::new(x)).collect())
+    }
+
+    #[test]
+    fn test_add_sub_mul() {
+        let a = fps::<998_244_353>(&[1, 2, 3]);
+        let b = fps(&[4, 5]);
+        assert_eq!(&a + &b, fps(&[5, 7, 3]));
+        assert_eq!(&b - &a, fps(&[3, 3, 998_244_350]));
+        assert_eq!(&a * &b, fps(&[4, 13, 22, 15]));
+        assert_eq!(a.clone() * b.clone(), fps(&[4, 13, 22, 15]));
+        assert_eq!(-b.clone(), fps(&[998_244_349, 998_244_348]));
+        assert_eq!(b.clone() * StaticModInt::new(2), fps(&[8, 10]));
+
+        let mut c = a.clone();
+        c += &b;
+        c -= &b;
+        c *= &b;
+        assert_eq!(c, &a * &b);
+
+        // 1_000_000_007 uses arbitrary modulus convolution
+        let a = Fps(sequence::<1_000_000_007>(100, 1));
+        let b = Fps(sequence::<1_000_000_007>(100, 2));
+        let c = &a * &b;
+        assert_eq!(c.len(), 199);
+        assert_eq!(c.0[0], a.0[0] * b.0[0]);
+        assert_eq!(c.0[198], a.0[99] * b.0[99]);
+    }
+
+    #[test]
+    fn test_default_modulus() {
+        // the bare Fps shares the default modulus with Mint
+        let f: Fps = Fps::from(vec![Mint::new(1), Mint::new(1_000_000_006)]);
+        assert_eq!(
+            &f * &f,
+            Fps::from([1, 1_000_000_005, 1].map(Mint::new).to_vec())
+        );
+    }
+
+    #[test]
+    fn test_truncate_prefix() {
+        let mut a = fps::<998_244_353>(&[1, 2, 3]);
+        assert_eq!(a.prefix(5), fps(&[1, 2, 3, 0, 0]));
+        assert_eq!(a.prefix(2), fps(&[1, 2]));
+        a.truncate(1);
+        assert_eq!(a, fps(&[1]));
+    }
+
+    #[test]
+    fn test_derivative_integral() {
+        let a = fps::<998_244_353>(&[1, 2, 3, 4]);
+        assert_eq!(a.derivative(), fps(&[2, 6, 12]));
+        assert_eq!(a.derivative().integral(), fps(&[0, 2, 3, 4]));
+        assert_eq!(Fps::<998_244_353>::zeros(0).integral(), fps(&[0]));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let a = fps::<998_244_353>(&[1, 2, 3]);
+        assert_eq!(a.evaluate(StaticModInt::new(0)), 1);
+        assert_eq!(a.evaluate(StaticModInt::new(2)), 17);
+        assert_eq!(
+            Fps::<998_244_353>::zeros(0).evaluate(StaticModInt::new(2)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 + 2x^2 + 3x + 4 = (x + 1)(x^2 + x + 2) + 2
+        let f = fps::<998_244_353>(&[4, 3, 2, 1]);
+        let g = fps(&[1, 1, 0]);
+        assert_eq!(f.div_rem(&g), (fps(&[2, 1, 1]), fps(&[2])));
+        assert_eq!(g.div_rem(&f), (fps(&[]), fps(&[1, 1, 0])));
+
+        for (n, m) in [(1, 1), (10, 3), (300, 100), (1000, 999)] {
+            let f = Fps(sequence::<1_000_000_007>(n, 1));
+            let g = Fps(sequence::<1_000_000_007>(m, 2));
+            let (q, r) = f.div_rem(&g);
+            assert_eq!(q.len(), n - m + 1);
+            assert_eq!(r.len(), m - 1);
+            assert_eq!((&(&q * &g) + &r).prefix(n), f);
+        }
+    }
+
+    #[test]
+    fn test_compose_x_pow() {
+        let a = fps::<998_244_353>(&[1, 2, 3]);
+        assert_eq!(a.compose_x_pow(1), a);
+        assert_eq!(a.compose_x_pow(3), fps(&[1, 0, 0, 2, 0, 0, 3]));
+    }
+
+    #[test]
+    fn test_inv() {
+        // 1 / (1 - x) = 1 + x + x^2 + ...
+        let a = fps::<998_244_353>(&[1, 998_244_352]);
+        assert_eq!(a.inv(5), fps(&[1, 1, 1, 1, 1]));
+
+        for n in [1, 2, 3, 100, 1000] {
+            let a = Fps(sequence::<998_244_353>(n, 3));
+            let b = a.inv(n);
+            assert_eq!((&a * &b).prefix(n), fps::<998_244_353>(&[1]).prefix(n));
+        }
+
+        let a = Fps(sequence::<1_000_000_007>(300, 3));
+        assert_eq!((&a * &a.inv(300)).prefix(300), fps(&[1]).prefix(300));
+    }
+
+    #[test]
+    fn test_exp_log() {
+        // exp(x) = sum of x^i / i!
+        let e = fps::<998_244_353>(&[0, 1]).exp(10);
+        let mut ifact = StaticModInt::new(1);
+        for i in 0..10 {
+            if i > 0 {
+                ifact /= StaticModInt::from(i);
+            }
+            assert_eq!(e.0[i], ifact);
+        }
+
+        // log(1 / (1 - x)) = sum of x^i / i
+        let l = fps::<998_244_353>(&[1, 998_244_352]).inv(10).log(10);
+        assert_eq!(l.0[0], 0);
+        for i in 1..10 {
+            assert_eq!(l.0[i], StaticModInt::from(i).inv());
+        }
+
+        for n in [1, 2, 3, 100, 1000] {
+            let mut a = Fps(sequence::<998_244_353>(n, 5));
+            a.0[0] = StaticModInt::new(0);
+            assert_eq!(a.exp(n).log(n), a);
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        let a = fps::<998_244_353>(&[0, 0, 3, 1, 4, 1, 5]);
+        let mut expected = fps(&[1]);
+        for k in 0..5 {
+            assert_eq!(a.pow(k, 20), expected.prefix(20));
+            expected *= &a;
+        }
+        assert_eq!(a.pow(10, 20), Fps::zeros(20));
+        assert_eq!(a.pow(1 << 60, 20), Fps::zeros(20));
+        assert_eq!(Fps::<998_244_353>::zeros(3).pow(0, 3), fps(&[1, 0, 0]));
+        assert_eq!(Fps::<998_244_353>::zeros(3).pow(2, 3), fps(&[0, 0, 0]));
+
+        // constant term is a huge power
+        let a = fps::<998_244_353>(&[2]);
+        let k = 1_000_000_000_000;
+        let c = StaticModInt::<998_244_353>::new(2).pow((k % 998_244_352) as u32);
+        assert_eq!(
+            a.pow(k, 3),
+            Fps(vec![c, StaticModInt::new(0), StaticModInt::new(0)])
+        );
+
+        let a = Fps(sequence::<1_000_000_007>(50, 1));
+        assert_eq!(a.pow(3, 50), (&(&a * &a) * &a).prefix(50));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let a = fps::<998_244_353>(&[0, 0, 4, 4, 1]);
+        assert_eq!(a.sqrt(3), Some(fps(&[0, 2, 1])));
+        assert_eq!(fps::<998_244_353>(&[0, 1]).sqrt(3), None);
+        assert_eq!(fps::<998_244_353>(&[3]).sqrt(3), None);
+        assert_eq!(Fps::<998_244_353>::zeros(2).sqrt(3), Some(Fps::zeros(3)));
+
+        // x^3 is 0 mod x^n for n <= 3
+        let a = fps::<998_244_353>(&[0, 0, 0, 1]);
+        assert_eq!(a.sqrt(1), Some(Fps::zeros(1)));
+        assert_eq!(a.sqrt(2), Some(Fps::zeros(2)));
+        assert_eq!(a.sqrt(3), Some(Fps::zeros(3)));
+        assert_eq!(a.sqrt(4), None);
+        assert_eq!(a.sqrt(0), Some(Fps::zeros(0)));
+
+        let a = Fps(sequence::<998_244_353>(500, 7));
+        let s = (&a * &a).prefix(500);
+        let r = s.sqrt(500).unwrap();
+        assert_eq!((&r * &r).prefix(500), s);
+
+        let a = Fps::<1_000_000_007>(vec![Mint::new(9), Mint::new(6), Mint::new(1)]);
+        assert_eq!(a.sqrt(2), Some(Fps(vec![Mint::new(3), Mint::new(1)])));
+    }
+}
@@ -1,9 +1,24 @@
 pub mod binary_search;
+pub mod binomial;
 pub mod bitvec;
+pub mod convolution;
+pub mod counting;
+pub mod crt;
+pub mod dynamic_mint;
+pub mod fps;
+pub mod linear_recurrence;
 pub mod macros;
+pub mod matrix;
 pub mod mint;
+pub mod mint64;
+pub mod modular;
+pub mod montgomery;
 pub mod multiset;
+pub mod polynomial;
 pub mod precalc;
 pub mod prime;
+pub mod quad_ext;
 pub mod segment_tree;
+#[cfg(test)]
+pub(crate) mod test_util;
 pub mod unionfind;
@@ -0,0 +1,173 @@
+use crate::fps::Fps;
+use crate::mint::StaticModInt;
+
+// Returns the shortest c such that s[i] = c[0] * s[i - 1] + ... + c[d - 1] * s[i - d] (i >= d)
+// by Berlekamp-Massey algorithm
+// (modulus should be a prime number, and s should have at least 2d terms to be determined)
+pub fn berlekamp_massey<const M: u32>(s: &[StaticModInt<M>]) -> Vec<StaticModInt<M>> {
+    // connection polynomial: s[i] + c[1] * s[i - 1] + ... + c[l] * s[i - l] = 0
+    let mut c = vec![StaticModInt::new(1)];
+    // c before the last change of l, and its discrepancy
+    let mut b = vec![StaticModInt::new(1)];
+    let mut b_disc = StaticModInt::new(1);
+    let mut l = 0;
+    let mut shift = 1;
+
+    for i in 0..s.len() {
+        let mut disc = s[i];
+        for j in 1..=l {
+            disc += c[j] * s[i - j];
+        }
+        if disc == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let coef = disc / b_disc;
+        let prev = c.clone();
+        if c.len() < b.len() + shift {
+            c.resize(b.len() + shift, StaticModInt::new(0));
+        }
+        for (j, x) in b.iter().enumerate() {
+            c[j + shift] -= coef * *x;
+        }
+
+        if 2 * l <= i {
+            l = i + 1 - l;
+            b = prev;
+            b_disc = disc;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    c.resize(l + 1, StaticModInt::new(0));
+    c[1..].iter().map(|x| -*x).collect()
+}
+
+// Returns [x^n] p / q by Bostan-Mori algorithm in O(d log d log n)
+// (constant term of q should not be 0)
+pub fn bostan_mori<const M: u32>(p: &Fps<M>, q: &Fps<M>, mut n: u64) -> StaticModInt<M> {
+    let mut p = p.clone();
+    let mut q = q.clone();
+    while n > 0 {
+        // p / q = p(x) q(-x) / q(x) q(-x), and the denominator has even terms only
+        let mut q_neg = q.clone();
+        for x in q_neg.0.iter_mut().skip(1).step_by(2) {
+            *x = -*x;
+        }
+        let u = &p * &q_neg;
+        let v = &q * &q_neg;
+
+        p = Fps(u.0.into_iter().skip((n & 1) as usize).step_by(2).collect());
+        q = Fps(v.0.into_iter().step_by(2).collect());
+        n >>= 1;
+    }
+
+    match p.0.first() {
+        Some(x) => *x / q.0[0],
+        None => StaticModInt::new(0),
+    }
+}
+
+// Returns a[n] where a[i] = c[0] * a[i - 1] + ... + c[d - 1] * a[i - d]
+// and a[0], ..., a[d - 1] are given as initial
+pub fn nth_term<const M: u32>(
+    c: &[StaticModInt<M>],
+    initial: &[StaticModInt<M>],
+    n: u64,
+) -> StaticModInt<M> {
+    let d = c.len();
+    assert!(initial.len() >= d);
+    if n < initial.len() as u64 {
+        return initial[n as usize];
+    }
+
+    // a(x) = p(x) / q(x) where q(x) = 1 - c[0] x - ... - c[d - 1] x^d
+    let mut q = Fps(vec![StaticModInt::new(1)]);
+    q.0.extend(c.iter().map(|x| -*x));
+    let p = (&Fps(initial[..d].to_vec()) * &q).prefix(d);
+
+    bostan_mori(&p, &q, n)
+}
+
+// Returns s[n] of the sequence which is guessed from its first terms by Berlekamp-Massey algorithm
+pub fn guess_nth_term<const M: u32>(s: &[StaticModInt<M>], n: u64) -> StaticModInt<M> {
+    nth_term(&berlekamp_massey(s), s, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::{Mint, Mint998244353};
+
+    fn generate<const M: u32>(
+        c: &[StaticModInt<M>],
+        initial: &[StaticModInt<M>],
+        n: usize,
+    ) -> Vec<StaticModInt<M>> {
+        let mut a = initial.to_vec();
+        while a.len() < n {
+            let i = a.len();
+            let next = c.iter().enumerate().map(|(j, x)| *x * a[i - 1 - j]).sum();
+            a.push(next);
+        }
+        a
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        let c = [1, 1].map(Mint998244353::new);
+        let s = generate(&c, &[0, 1].map(Mint998244353::new), 20);
+        assert_eq!(berlekamp_massey(&s), c.to_vec());
+        assert_eq!(nth_term(&c, &s[..2], 10), 55);
+        assert_eq!(guess_nth_term(&s, 1_000_000_000_000_000_000), 23_849_548);
+
+        let c = [1, 1].map(Mint::new);
+        let s = generate(&c, &[0, 1].map(Mint::new), 20);
+        assert_eq!(guess_nth_term(&s, 1_000_000_000_000_000_000), 209_783_453);
+    }
+
+    #[test]
+    fn test_random_recurrence() {
+        let c = [3, 0, 123_456_789, 7, 998_244_352, 42].map(Mint998244353::new);
+        let initial = [1, 2, 3, 4, 5, 6].map(Mint998244353::new);
+        let s = generate(&c, &initial, 300);
+
+        assert_eq!(berlekamp_massey(&s[..12]), c.to_vec());
+        for n in 0..300 {
+            assert_eq!(nth_term(&c, &initial, n as u64), s[n]);
+            assert_eq!(guess_nth_term(&s[..12], n as u64), s[n]);
+        }
+
+        let c = (0..100u32)
+            .map(|i| Mint::new(i * i + 1))
+            .collect::<Vec<_>>();
+        let initial = (0..100u32).map(|i| Mint::new(i * 7919)).collect::<Vec<_>>();
+        let s = generate(&c, &initial, 400);
+        assert_eq!(berlekamp_massey(&s), c);
+        assert_eq!(nth_term(&c, &initial, 399), s[399]);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(berlekamp_massey::<998_244_353>(&[]).is_empty());
+        assert!(berlekamp_massey(&[0, 0, 0].map(Mint::new)).is_empty());
+        assert_eq!(guess_nth_term(&[0, 0, 0].map(Mint::new), 100), 0);
+
+        // 2^n
+        let s = [1, 2, 4, 8].map(Mint::new);
+        assert_eq!(berlekamp_massey(&s), vec![Mint::new(2)]);
+        assert_eq!(guess_nth_term(&s, 100), Mint::new(2).pow(100));
+    }
+
+    #[test]
+    fn test_bostan_mori() {
+        // 1 / (1 - x)^2 = sum of (n + 1) x^n
+        let p = Fps(vec![Mint::new(1)]);
+        let q = Fps([1, 1_000_000_005, 1].map(Mint::new).to_vec());
+        assert_eq!(bostan_mori(&p, &q, 0), 1);
+        assert_eq!(bostan_mori(&p, &q, 1_000_000_000_000), 999_993_008);
+    }
+}
@@ -0,0 +1,414 @@
+use crate::mint::{StaticModInt, DEFAULT_MODULUS};
+
+// Dense matrix over StaticModInt<M>
+// (modulus should be a prime number for determinant, rank, inverse and solve)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<const M: u32 = DEFAULT_MODULUS> {
+    rows: usize,
+    cols: usize,
+    data: Vec<Vec<StaticModInt<M>>>,
+}
+
+impl<const M: u32> From<Vec<Vec<StaticModInt<M>>>> for Matrix<M> {
+    fn from(data: Vec<Vec<StaticModInt<M>>>) -> Self {
+        let rows = data.len();
+        let cols = data.first().map_or(0, |row| row.len());
+        assert!(data.iter().all(|row| row.len() == cols));
+        Self { rows, cols, data }
+    }
+}
+
+impl<const M: u32> std::ops::Index<usize> for Matrix<M> {
+    type Output = Vec<StaticModInt<M>>;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.data[i]
+    }
+}
+
+impl<const M: u32> std::ops::IndexMut<usize> for Matrix<M> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        &mut self.data[i]
+    }
+}
+
+impl<const M: u32> std::ops::Add for &Matrix<M> {
+    type Output = Matrix<M>;
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols));
+        let mut c = self.clone();
+        for (row, rhs_row) in c.data.iter_mut().zip(rhs.data.iter()) {
+            for (x, y) in row.iter_mut().zip(rhs_row.iter()) {
+                *x += *y;
+            }
+        }
+        c
+    }
+}
+
+impl<const M: u32> std::ops::Sub for &Matrix<M> {
+    type Output = Matrix<M>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols));
+        let mut c = self.clone();
+        for (row, rhs_row) in c.data.iter_mut().zip(rhs.data.iter()) {
+            for (x, y) in row.iter_mut().zip(rhs_row.iter()) {
+                *x -= *y;
+            }
+        }
+        c
+    }
+}
+
+impl<const M: u32> std::ops::Mul for &Matrix<M> {
+    type Output = Matrix<M>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows);
+        let mut c = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let x = self.data[i][k];
+                if x == 0 {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    c.data[i][j] += x * rhs.data[k][j];
+                }
+            }
+        }
+        c
+    }
+}
+
+impl<const M: u32> std::ops::Mul for Matrix<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[allow(dead_code)]
+impl<const M: u32> Matrix<M> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![vec![StaticModInt::new(0); cols]; rows],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.data[i][i] = StaticModInt::new(1);
+        }
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // Returns self * v as a column vector
+    pub fn apply(&self, v: &[StaticModInt<M>]) -> Vec<StaticModInt<M>> {
+        assert_eq!(self.cols, v.len());
+        self.data
+            .iter()
+            .map(|row| row.iter().zip(v.iter()).map(|(x, y)| *x * *y).sum())
+            .collect()
+    }
+
+    pub fn pow(&self, mut n: u64) -> Self {
+        assert_eq!(self.rows, self.cols);
+        let mut t = Self::identity(self.rows);
+        let mut s = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                t = &t * &s;
+            }
+            s = &s * &s;
+            n >>= 1;
+        }
+        t
+    }
+
+    // Transforms the first `cols` columns into reduced row echelon form by Gauss-Jordan elimination
+    // (the remaining columns follow the same row operations)
+    // Returns the pivot columns and the product of the pivots with the sign of row swaps.
+    fn eliminate(&mut self, cols: usize) -> (Vec<usize>, StaticModInt<M>) {
+        let mut pivots = vec![];
+        let mut det = StaticModInt::new(1);
+
+        for c in 0..cols {
+            let r = pivots.len();
+            if r == self.rows {
+                break;
+            }
+
+            let p = match (r..self.rows).find(|&i| self.data[i][c] != 0) {
+                Some(p) => p,
+                None => continue,
+            };
+            if p != r {
+                self.data.swap(p, r);
+                det = -det;
+            }
+
+            let pivot = self.data[r][c];
+            det *= pivot;
+            let pivot_inv = pivot.inv();
+            for x in self.data[r].iter_mut() {
+                *x *= pivot_inv;
+            }
+
+            let pivot_row = self.data[r].clone();
+            for (i, row) in self.data.iter_mut().enumerate() {
+                let f = row[c];
+                if i == r || f == 0 {
+                    continue;
+                }
+                for (x, y) in row.iter_mut().zip(pivot_row.iter()).skip(c) {
+                    *x -= f * *y;
+                }
+            }
+
+            pivots.push(c);
+        }
+
+        (pivots, det)
+    }
+
+    pub fn determinant(&self) -> StaticModInt<M> {
+        assert_eq!(self.rows, self.cols);
+        let (pivots, det) = self.clone().eliminate(self.cols);
+        if pivots.len() < self.rows {
+            StaticModInt::new(0)
+        } else {
+            det
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().eliminate(self.cols).0.len()
+    }
+
+    // Returns the inverse matrix, or None when self is singular
+    pub fn inverse(&self) -> Option<Self> {
+        assert_eq!(self.rows, self.cols);
+        let n = self.rows;
+
+        // [self | I] -> [I | self^-1]
+        let mut aug = Self::zeros(n, 2 * n);
+        for i in 0..n {
+            aug.data[i][..n].copy_from_slice(&self.data[i]);
+            aug.data[i][n + i] = StaticModInt::new(1);
+        }
+        if aug.eliminate(n).0.len() < n {
+            return None;
+        }
+
+        Some(Self {
+            rows: n,
+            cols: n,
+            data: aug.data.into_iter().map(|row| row[n..].to_vec()).collect(),
+        })
+    }
+
+    // Solves self * x = b
+    // Returns a solution and a basis of the null space (every solution is the solution plus
+    // a linear combination of the basis), or None when there is no solution.
+    #[allow(clippy::type_complexity)]
+    pub fn solve(
+        &self,
+        b: &[StaticModInt<M>],
+    ) -> Option<(Vec<StaticModInt<M>>, Vec<Vec<StaticModInt<M>>>)> {
+        assert_eq!(self.rows, b.len());
+        let m = self.cols;
+
+        let mut aug = Self::zeros(self.rows, m + 1);
+        for (row, (a_row, y)) in aug.data.iter_mut().zip(self.data.iter().zip(b.iter())) {
+            row[..m].copy_from_slice(a_row);
+            row[m] = *y;
+        }
+        let (pivots, _) = aug.eliminate(m);
+
+        // rows below the rank are 0 = aug[i][m]
+        if aug.data[pivots.len()..].iter().any(|row| row[m] != 0) {
+            return None;
+        }
+
+        let mut x = vec![StaticModInt::new(0); m];
+        for (i, &c) in pivots.iter().enumerate() {
+            x[c] = aug.data[i][m];
+        }
+
+        let mut is_pivot = vec![false; m];
+        for &c in pivots.iter() {
+            is_pivot[c] = true;
+        }
+        let basis = (0..m)
+            .filter(|&f| !is_pivot[f])
+            .map(|f| {
+                let mut v = vec![StaticModInt::new(0); m];
+                v[f] = StaticModInt::new(1);
+                for (i, &c) in pivots.iter().enumerate() {
+                    v[c] = -aug.data[i][f];
+                }
+                v
+            })
+            .collect();
+
+        Some((x, basis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::Mint;
+
+    fn matrix(v: &[&[u32]]) -> Matrix {
+        Matrix::from(
+            v.iter()
+                .map(|row| row.iter().map(|&x| Mint::new(x)).collect())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn sequence_matrix(rows: usize, cols: usize, seed: u32) -> Matrix {
+        let mut m = Matrix::zeros(rows, cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                m[i][j] = Mint::new((i as u32 * 7919 + j as u32 * 104_729 + seed) % 17);
+            }
+        }
+        m
+    }
+
+    // determinant by cofactor expansion
+    fn brute_force_det(m: &Matrix) -> Mint {
+        let n = m.rows();
+        if n == 0 {
+            return Mint::new(1);
+        }
+        let mut det = Mint::new(0);
+        for j in 0..n {
+            let minor = Matrix::from(
+                (1..n)
+                    .map(|i| (0..n).filter(|&k| k != j).map(|k| m[i][k]).collect())
+                    .collect::<Vec<_>>(),
+            );
+            let term = m[0][j] * brute_force_det(&minor);
+            if j % 2 == 0 {
+                det += term;
+            } else {
+                det -= term;
+            }
+        }
+        det
+    }
+
+    #[test]
+    fn test_add_sub_mul() {
+        let a = matrix(&[&[1, 2], &[3, 4]]);
+        let b = matrix(&[&[5, 6], &[7, 8]]);
+        assert_eq!(&a + &b, matrix(&[&[6, 8], &[10, 12]]));
+        assert_eq!(&b - &a, matrix(&[&[4, 4], &[4, 4]]));
+        assert_eq!(&a * &b, matrix(&[&[19, 22], &[43, 50]]));
+
+        let c = matrix(&[&[1, 2, 3]]);
+        assert_eq!(&c * &matrix(&[&[1], &[1], &[1]]), matrix(&[&[6]]));
+        assert_eq!(
+            a.apply(&[Mint::new(1), Mint::new(1)]),
+            vec![Mint::new(3), Mint::new(7)]
+        );
+    }
+
+    #[test]
+    fn test_pow() {
+        let fib = matrix(&[&[1, 1], &[1, 0]]);
+        assert_eq!(fib.pow(0), Matrix::identity(2));
+        assert_eq!(fib.pow(10)[0][1], 55);
+        assert_eq!(fib.pow(1_000_000_000_000_000_000)[0][1], 209_783_453);
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(matrix(&[&[1, 2], &[3, 4]]).determinant(), Mint::MODULUS - 2);
+        assert_eq!(matrix(&[&[0, 1], &[1, 0]]).determinant(), Mint::MODULUS - 1);
+        assert_eq!(matrix(&[&[1, 2], &[2, 4]]).determinant(), 0);
+        assert_eq!(Matrix::<1_000_000_007>::identity(0).determinant(), 1);
+
+        for n in 1..=6 {
+            for seed in 0..5 {
+                let m = sequence_matrix(n, n, seed);
+                assert_eq!(m.determinant(), brute_force_det(&m));
+            }
+        }
+
+        // Kirchhoff's theorem: the complete graph K4 has 4^2 spanning trees
+        let laplacian_minor = matrix(&[
+            &[3, Mint::MODULUS - 1, Mint::MODULUS - 1],
+            &[Mint::MODULUS - 1, 3, Mint::MODULUS - 1],
+            &[Mint::MODULUS - 1, Mint::MODULUS - 1, 3],
+        ]);
+        assert_eq!(laplacian_minor.determinant(), 16);
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(matrix(&[&[1, 2], &[2, 4]]).rank(), 1);
+        assert_eq!(matrix(&[&[0, 0], &[0, 0]]).rank(), 0);
+        assert_eq!(matrix(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]).rank(), 2);
+        assert_eq!(matrix(&[&[1, 2, 3], &[4, 5, 6]]).rank(), 2);
+        assert_eq!(Matrix::<1_000_000_007>::identity(5).rank(), 5);
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(matrix(&[&[1, 2], &[2, 4]]).inverse(), None);
+        for n in 1..=8 {
+            for seed in 0..5 {
+                let m = sequence_matrix(n, n, seed);
+                match m.inverse() {
+                    Some(inv) => {
+                        assert_eq!(&m * &inv, Matrix::identity(n));
+                        assert_eq!(&inv * &m, Matrix::identity(n));
+                    }
+                    None => assert_eq!(m.determinant(), 0),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        // unique solution
+        let a = matrix(&[&[2, 1], &[1, 3]]);
+        let (x, basis) = a.solve(&[Mint::new(5), Mint::new(10)]).unwrap();
+        assert_eq!(x, vec![Mint::new(1), Mint::new(3)]);
+        assert!(basis.is_empty());
+
+        // no solution
+        let a = matrix(&[&[1, 2], &[2, 4]]);
+        assert_eq!(a.solve(&[Mint::new(1), Mint::new(3)]), None);
+
+        // underdetermined
+        for (rows, cols) in [(1, 3), (2, 5), (4, 4), (5, 3)] {
+            for seed in 0..5 {
+                let a = sequence_matrix(rows, cols, seed);
+                let b = a.apply(&(0..cols as u32).map(Mint::new).collect::<Vec<_>>());
+                let (x, basis) = a.solve(&b).unwrap();
+                assert_eq!(a.apply(&x), b);
+                assert_eq!(basis.len(), cols - a.rank());
+                for v in basis.iter() {
+                    assert_eq!(a.apply(v), vec![Mint::new(0); rows]);
+                }
+            }
+        }
+    }
+}
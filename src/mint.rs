@@ -1,59 +1,132 @@
-#[derive(Clone, Copy, Debug)]
-pub struct Mint {
+// Modular integer whose modulus is given as a const generic parameter,
+// so that values under different moduli can coexist in one binary
+#[derive(Clone, Copy, Default)]
+pub struct StaticModInt<const M: u32> {
     value: u32,
 }
 
-impl std::ops::Add for Mint {
-    type Output = Mint;
-    fn add(self, rhs: Mint) -> Self::Output {
+// Modulus of Mint, also used as the default of types generic over the modulus
+pub const DEFAULT_MODULUS: u32 = 1_000_000_007;
+
+// modulus should be less than 2^31
+// (a composite modulus is fine, but only values coprime to it have an inverse)
+pub type Mint = StaticModInt<DEFAULT_MODULUS>;
+pub type Mint998244353 = StaticModInt<998_244_353>;
+
+impl<const M: u32> std::ops::Add for StaticModInt<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
         let mut d = self.value + rhs.value;
         if d >= Self::MODULUS {
             d -= Self::MODULUS;
         }
-        Mint::new(d)
+        Self::new(d)
     }
 }
 
-impl std::ops::AddAssign for Mint {
-    fn add_assign(&mut self, rhs: Mint) {
+impl<const M: u32> std::ops::AddAssign for StaticModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
 }
 
-impl std::ops::Sub for Mint {
-    type Output = Mint;
-    fn sub(self, rhs: Mint) -> Self::Output {
+impl<const M: u32> std::ops::Sub for StaticModInt<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
         let mut d = self.value + Self::MODULUS - rhs.value;
         if d >= Self::MODULUS {
             d -= Self::MODULUS;
         }
-        Mint::new(d)
+        Self::new(d)
     }
 }
 
-impl std::ops::SubAssign for Mint {
-    fn sub_assign(&mut self, rhs: Mint) {
+impl<const M: u32> std::ops::SubAssign for StaticModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
         *self = *self - rhs;
     }
 }
 
-impl std::ops::Mul for Mint {
-    type Output = Mint;
-    fn mul(self, rhs: Mint) -> Self::Output {
+impl<const M: u32> std::ops::Mul for StaticModInt<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
         let d = self.value as u64 * rhs.value as u64 % Self::MODULUS as u64;
 
-        Mint::new(d as u32)
+        Self::new(d as u32)
     }
 }
 
-impl std::ops::MulAssign for Mint {
-    fn mul_assign(&mut self, rhs: Mint) {
+impl<const M: u32> std::ops::MulAssign for StaticModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
         *self = *self * rhs;
     }
 }
 
-impl std::ops::Neg for Mint {
-    type Output = Mint;
+impl<const M: u32> std::ops::Div for StaticModInt<M> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl<const M: u32> std::ops::DivAssign for StaticModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+// Implements binary operators which take references of StaticModInt
+macro_rules! static_modint_ref_ops {
+    ($($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident;)*) => {
+        $(
+            impl<const M: u32> std::ops::$trait<&StaticModInt<M>> for StaticModInt<M> {
+                type Output = Self;
+                fn $method(self, rhs: &Self) -> Self::Output {
+                    std::ops::$trait::$method(self, *rhs)
+                }
+            }
+
+            impl<const M: u32> std::ops::$trait<StaticModInt<M>> for &StaticModInt<M> {
+                type Output = StaticModInt<M>;
+                fn $method(self, rhs: StaticModInt<M>) -> Self::Output {
+                    std::ops::$trait::$method(*self, rhs)
+                }
+            }
+
+            impl<const M: u32> std::ops::$trait<&StaticModInt<M>> for &StaticModInt<M> {
+                type Output = StaticModInt<M>;
+                fn $method(self, rhs: &StaticModInt<M>) -> Self::Output {
+                    std::ops::$trait::$method(*self, *rhs)
+                }
+            }
+
+            impl<const M: u32> std::ops::$assign_trait<&StaticModInt<M>> for StaticModInt<M> {
+                fn $assign_method(&mut self, rhs: &Self) {
+                    std::ops::$assign_trait::$assign_method(self, *rhs)
+                }
+            }
+        )*
+    };
+}
+
+static_modint_ref_ops! {
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+}
+
+impl<const M: u32> std::ops::Neg for &StaticModInt<M> {
+    type Output = StaticModInt<M>;
+
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
+
+impl<const M: u32> std::ops::Neg for StaticModInt<M> {
+    type Output = Self;
 
     fn neg(self) -> Self::Output {
         let d = match self.value {
@@ -61,41 +134,178 @@ impl std::ops::Neg for Mint {
             _ => Self::MODULUS - self.value,
         };
 
-        Mint::new(d)
+        Self::new(d)
     }
 }
 
-impl std::fmt::Display for Mint {
+impl<const M: u32> std::fmt::Display for StaticModInt<M> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", self.value)
     }
 }
 
-impl From<usize> for Mint {
+// {:#?} shows the value as a small fraction if there is one (e.g. 500000004 -> 1/2)
+impl<const M: u32> std::fmt::Debug for StaticModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !f.alternate() {
+            return f
+                .debug_struct("StaticModInt")
+                .field("value", &self.value)
+                .finish();
+        }
+        match self.to_fraction() {
+            Some((p, 1)) => write!(f, "{}", p),
+            Some((p, q)) => write!(f, "{}/{}", p, q),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl<const M: u32> From<usize> for StaticModInt<M> {
     fn from(val: usize) -> Self {
         let d = (val % Self::MODULUS as usize) as u32;
 
-        Mint::new(d)
+        Self::new(d)
+    }
+}
+
+impl<const M: u32> From<u32> for StaticModInt<M> {
+    fn from(val: u32) -> Self {
+        Self::new(val)
+    }
+}
+
+impl<const M: u32> From<u64> for StaticModInt<M> {
+    fn from(val: u64) -> Self {
+        let d = (val % Self::MODULUS as u64) as u32;
+
+        Self::new(d)
+    }
+}
+
+// negative values are mapped to their non-negative residues (e.g. -1 -> MODULUS - 1)
+impl<const M: u32> From<i64> for StaticModInt<M> {
+    fn from(val: i64) -> Self {
+        let d = val.rem_euclid(Self::MODULUS as i64) as u32;
+
+        Self::new(d)
+    }
+}
+
+impl<const M: u32> From<i32> for StaticModInt<M> {
+    fn from(val: i32) -> Self {
+        Self::from(val as i64)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseModIntError;
+
+impl std::fmt::Display for ParseModIntError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid decimal integer for modular integer")
+    }
+}
+
+impl std::error::Error for ParseModIntError {}
+
+// Parses a decimal integer of any length (with an optional sign) and returns its residue modulo m
+// (shared by FromStr of the modular integer types, which makes them readable by proconio::input!)
+pub fn parse_mod(s: &str, m: u64) -> Result<u64, ParseModIntError> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    if digits.is_empty() {
+        return Err(ParseModIntError);
+    }
+
+    let mut d = 0u128;
+    for c in digits.bytes() {
+        if !c.is_ascii_digit() {
+            return Err(ParseModIntError);
+        }
+        d = (d * 10 + (c - b'0') as u128) % m as u128;
+    }
+
+    let d = d as u64;
+    Ok(if negative && d > 0 { m - d } else { d })
+}
+
+impl<const M: u32> std::str::FromStr for StaticModInt<M> {
+    type Err = ParseModIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_mod(s, Self::MODULUS as u64).map(|d| Self::new(d as u32))
+    }
+}
+
+impl<const M: u32> std::iter::Sum for StaticModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), |acc, x| acc + x)
+    }
+}
+
+impl<'a, const M: u32> std::iter::Sum<&'a Self> for StaticModInt<M> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), |acc, x| acc + x)
+    }
+}
+
+impl<const M: u32> std::iter::Product for StaticModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |acc, x| acc * x)
+    }
+}
+
+impl<'a, const M: u32> std::iter::Product<&'a Self> for StaticModInt<M> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |acc, x| acc * x)
+    }
+}
+
+impl<const M: u32> num_traits::Zero for StaticModInt<M> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl<const M: u32> num_traits::One for StaticModInt<M> {
+    fn one() -> Self {
+        Self::new(1)
     }
 }
 
-impl PartialEq<Self> for Mint {
+impl<const M: u32> PartialEq<Self> for StaticModInt<M> {
     fn eq(&self, other: &Self) -> bool {
         self.value == other.value
     }
 }
 
-impl PartialEq<u32> for Mint {
+impl<const M: u32> PartialEq<u32> for StaticModInt<M> {
     fn eq(&self, other: &u32) -> bool {
         self.value == *other
     }
 }
 
+impl<const M: u32> Eq for StaticModInt<M> {}
+
+impl<const M: u32> std::hash::Hash for StaticModInt<M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
 #[allow(dead_code)]
-impl Mint {
-    // to change modulus, rewrite this value
-    // (modulus should be a prime number)
-    pub const MODULUS: u32 = 1_000_000_007;
+impl<const M: u32> StaticModInt<M> {
+    pub const MODULUS: u32 = M;
+
+    // Euler's totient function of MODULUS
+    pub const TOTIENT: u32 = totient(M);
 
     pub fn new(n: u32) -> Self {
         Self {
@@ -103,8 +313,8 @@ impl Mint {
         }
     }
 
-    pub fn pow(self, mut n: u32) -> Mint {
-        let mut t = Mint::new(1);
+    pub fn pow(self, mut n: u32) -> Self {
+        let mut t = Self::new(1);
         let mut s = self;
         while n > 0 {
             if n & 1 == 1 {
@@ -116,14 +326,165 @@ impl Mint {
         t
     }
 
-    pub fn inv(self) -> Mint {
-        assert!(self.value > 0);
-        self.pow(Self::MODULUS - 2)
+    // a^n = a^(n mod TOTIENT + TOTIENT) holds for every a when n >= 31
+    // (generalized Euler's theorem, since MODULUS < 2^31 has no prime power factor above 2^30)
+    pub fn pow_u64(self, n: u64) -> Self {
+        if n <= u32::MAX as u64 {
+            return self.pow(n as u32);
+        }
+        self.pow((n % Self::TOTIENT as u64) as u32 + Self::TOTIENT)
+    }
+
+    // negative exponent means the power of the inverse
+    pub fn pow_i64(self, n: i64) -> Self {
+        if n < 0 {
+            self.inv().pow_u64(n.unsigned_abs())
+        } else {
+            self.pow_u64(n as u64)
+        }
+    }
+
+    // For exponents such as towers of powers which do not fit in u64
+    pub fn pow_biguint(self, n: &num_bigint::BigUint) -> Self {
+        match u64::try_from(n) {
+            Ok(n) => self.pow_u64(n),
+            Err(_) => {
+                let r = u32::try_from(n % Self::TOTIENT).unwrap();
+                self.pow(r + Self::TOTIENT)
+            }
+        }
+    }
+
+    // Returns the inverse, or None when gcd(value, MODULUS) != 1
+    pub fn checked_inv(self) -> Option<Self> {
+        inv_mod(self.value as u64, Self::MODULUS as u64).map(|x| Self::new(x as u32))
+    }
+
+    pub fn inv(self) -> Self {
+        self.checked_inv()
+            .expect("value is not invertible under the modulus")
     }
 
     pub fn value(&self) -> u32 {
         self.value
     }
+
+    // Returns p / q such that p = q * self, where |p| and q are at most 1000
+    // and q is coprime to MODULUS (such a fraction is unique if exists),
+    // by continued fraction expansion of self / MODULUS
+    // (the bound is small so that a random residue rarely looks like a fraction)
+    pub fn to_fraction(self) -> Option<(i64, u32)> {
+        let bound = std::cmp::min(1000, ((M / 2) as f64).sqrt() as i64);
+
+        // invariant: r = t * self (mod MODULUS)
+        let (mut r0, mut r1) = (M as i64, self.value as i64);
+        let (mut t0, mut t1) = (0i64, 1i64);
+        while r1 > bound {
+            let u = r0 / r1;
+            (r0, r1) = (r1, r0 - u * r1);
+            (t0, t1) = (t1, t0 - u * t1);
+        }
+
+        let (p, q) = if t1 < 0 { (-r1, -t1) } else { (r1, t1) };
+        if q > bound || inv_mod(q as u64, M as u64).is_none() {
+            return None;
+        }
+        Some((p, q as u32))
+    }
+}
+
+const fn totient(m: u32) -> u32 {
+    let mut n = m;
+    let mut phi = m;
+    let mut p = 2;
+    while p * p <= n {
+        if n % p == 0 {
+            phi = phi / p * (p - 1);
+            while n % p == 0 {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    if n > 1 {
+        phi = phi / n * (n - 1);
+    }
+    phi
+}
+
+// Returns p such that p[i] = a[0] * ... * a[i - 1] (p.len() = a.len() + 1)
+pub fn prefix_products<const M: u32>(a: &[StaticModInt<M>]) -> Vec<StaticModInt<M>> {
+    let mut p = Vec::with_capacity(a.len() + 1);
+    p.push(StaticModInt::new(1));
+    for (i, x) in a.iter().enumerate() {
+        p.push(p[i] * *x);
+    }
+    p
+}
+
+// Returns s such that s[i] = a[i] * ... * a[n - 1] (s.len() = a.len() + 1)
+pub fn suffix_products<const M: u32>(a: &[StaticModInt<M>]) -> Vec<StaticModInt<M>> {
+    let mut s = vec![StaticModInt::new(1); a.len() + 1];
+    for i in (0..a.len()).rev() {
+        s[i] = s[i + 1] * a[i];
+    }
+    s
+}
+
+// Returns the inverses of all elements with a single inversion (Montgomery's trick),
+// or Err(i) where a[i] is the first element which is not invertible
+pub fn checked_batch_inv<const M: u32>(
+    a: &[StaticModInt<M>],
+) -> Result<Vec<StaticModInt<M>>, usize> {
+    let p = prefix_products(a);
+    let mut t = match p[a.len()].checked_inv() {
+        Some(t) => t,
+        None => return Err(a.iter().position(|x| x.checked_inv().is_none()).unwrap()),
+    };
+
+    // t = (a[0] * ... * a[i])^-1 at the beginning of each step
+    let mut ret = vec![StaticModInt::new(0); a.len()];
+    for i in (0..a.len()).rev() {
+        ret[i] = t * p[i];
+        t *= a[i];
+    }
+    Ok(ret)
+}
+
+// checked_batch_inv which panics on an element which is not invertible
+pub fn batch_inv<const M: u32>(a: &[StaticModInt<M>]) -> Vec<StaticModInt<M>> {
+    checked_batch_inv(a).unwrap_or_else(|i| {
+        panic!(
+            "a[{}] = {} is not invertible under the modulus",
+            i, a[i].value
+        )
+    })
+}
+
+// Returns x such that a * x = 1 (mod m) by extended Euclidean algorithm,
+// or None when a and m are not coprime
+pub fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    assert!(m > 0);
+
+    // invariants: s = x0 * a (mod m), t = x1 * a (mod m)
+    let (mut s, mut t) = (m as i128, (a % m) as i128);
+    let (mut x0, mut x1) = (0i128, 1i128);
+    while t != 0 {
+        let u = s / t;
+        s -= t * u;
+        x0 -= x1 * u;
+        std::mem::swap(&mut s, &mut t);
+        std::mem::swap(&mut x0, &mut x1);
+    }
+
+    // s is gcd(a, m) here
+    if s != 1 {
+        return None;
+    }
+    if x0 < 0 {
+        x0 += m as i128;
+    }
+    Some(x0 as u64)
 }
 
 #[cfg(test)]
@@ -297,4 +658,344 @@ mod tests {
         let m = Mint::new(Mint::MODULUS - 1);
         assert_eq!(m.value(), 1_000_000_006);
     }
+
+    #[test]
+    fn test_other_modulus() {
+        let m = Mint998244353::new(998_244_352);
+        assert_eq!((m + Mint998244353::new(2)).value(), 1);
+        assert_eq!(m.pow(2), 1);
+        assert_eq!(Mint998244353::new(2).inv(), 499_122_177);
+        assert_eq!(Mint998244353::MODULUS, 998_244_353);
+
+        // different moduli coexist
+        assert_eq!(Mint::new(2).inv(), 500_000_004);
+        assert_eq!(StaticModInt::<7>::new(10), 3);
+    }
+
+    #[test]
+    fn test_inv_mod() {
+        assert_eq!(inv_mod(3, 7), Some(5));
+        assert_eq!(inv_mod(10, 7), Some(5));
+        assert_eq!(inv_mod(0, 7), None);
+        assert_eq!(inv_mod(4, 6), None);
+        assert_eq!(inv_mod(5, 1), Some(0));
+
+        let m = (1 << 61) - 1;
+        let x = inv_mod(12_345, m).unwrap();
+        assert_eq!(12_345 * x as u128 % m as u128, 1);
+
+        for m in 1..50u64 {
+            for a in 0..m {
+                let expected = (0..m).find(|&x| a * x % m == 1 % m);
+                assert_eq!(inv_mod(a, m), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_checked_inv() {
+        assert_eq!(Mint::new(0).checked_inv(), None);
+        assert_eq!(Mint::new(2).checked_inv(), Some(Mint::new(500_000_004)));
+
+        // composite modulus
+        type M = StaticModInt<1_000_000_000>;
+        assert_eq!(M::new(2).checked_inv(), None);
+        assert_eq!(M::new(5).checked_inv(), None);
+        assert_eq!(M::new(3).inv(), 666_666_667);
+        assert_eq!(M::new(999_999_999).inv(), 999_999_999);
+
+        type N = StaticModInt<{ 1 << 20 }>;
+        assert_eq!(N::new(4).checked_inv(), None);
+        assert_eq!(N::new(12_345).inv() * N::new(12_345), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inv_zero() {
+        Mint::new(0).inv();
+    }
+
+    #[test]
+    fn test_div() {
+        let ans = Mint::new(10) / Mint::new(5);
+        assert_eq!(ans.value, 2);
+
+        let ans = Mint::new(1) / Mint::new(2);
+        assert_eq!(ans.value, 500_000_004);
+
+        let mut l = Mint::new(3);
+        l /= Mint::new(3);
+        assert_eq!(l.value, 1);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops() {
+        let l = Mint::new(12);
+        let r = Mint::new(4);
+        assert_eq!(&l + &r, 16);
+        assert_eq!(l + &r, 16);
+        assert_eq!(&l + r, 16);
+        assert_eq!(&l - &r, 8);
+        assert_eq!(&l * &r, 48);
+        assert_eq!(&l / &r, 3);
+        assert_eq!(-&r, Mint::MODULUS - 4);
+
+        let mut m = Mint::new(12);
+        m += &r;
+        m -= &r;
+        m *= &r;
+        m /= &r;
+        assert_eq!(m, 12);
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let v = (1..=10).map(Mint::from).collect::<Vec<_>>();
+        assert_eq!(v.iter().sum::<Mint>(), 55);
+        assert_eq!(v.iter().copied().sum::<Mint>(), 55);
+        assert_eq!(v.iter().product::<Mint>(), 3_628_800);
+        assert_eq!(v.into_iter().product::<Mint>(), 3_628_800);
+
+        assert_eq!(std::iter::empty::<Mint>().sum::<Mint>(), 0);
+        assert_eq!(std::iter::empty::<Mint>().product::<Mint>(), 1);
+    }
+
+    #[test]
+    fn test_from_integers() {
+        assert_eq!(Mint::from(5u32).value, 5);
+        assert_eq!(Mint::from(u32::MAX).value, 294_967_267);
+        assert_eq!(Mint::from(u64::MAX).value, 582_344_007);
+        assert_eq!(Mint::from(-1i64).value, Mint::MODULUS - 1);
+        assert_eq!(Mint::from(-1_000_000_007i64).value, 0);
+        assert_eq!(Mint::from(i64::MIN).value, 708_828_003);
+        assert_eq!(Mint::from(-3i32).value, Mint::MODULUS - 3);
+        assert_eq!(Mint::from(i32::MIN).value, 852_516_373);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("123".parse::<Mint>(), Ok(Mint::new(123)));
+        assert_eq!("+123".parse::<Mint>(), Ok(Mint::new(123)));
+        assert_eq!("-1".parse::<Mint>(), Ok(Mint::new(Mint::MODULUS - 1)));
+        assert_eq!("1000000007".parse::<Mint>(), Ok(Mint::new(0)));
+        assert_eq!(
+            "123456789012345678901234567890".parse::<Mint>(),
+            Ok(Mint::new(197_434_842))
+        );
+        assert_eq!("".parse::<Mint>(), Err(ParseModIntError));
+        assert_eq!("-".parse::<Mint>(), Err(ParseModIntError));
+        assert_eq!("12a".parse::<Mint>(), Err(ParseModIntError));
+        assert_eq!("-0".parse::<Mint>(), Ok(Mint::new(0)));
+    }
+
+    #[test]
+    fn test_input() {
+        use proconio::input;
+        use proconio::source::once::OnceSource;
+
+        let source = OnceSource::from("4\n1 -1 1000000008 -123456789012345678901234567890\n");
+        input! {
+            from source,
+            n: usize,
+            a: [Mint; n],
+        }
+        assert_eq!(a, [1, 1_000_000_006, 1, 802_565_165].map(Mint::new));
+    }
+
+    #[test]
+    fn test_eq_hash() {
+        let mut set = std::collections::HashSet::new();
+        set.insert(Mint::new(1));
+        set.insert(Mint::new(Mint::MODULUS + 1));
+        set.insert(Mint::new(2));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_default_zero_one() {
+        use num_traits::{One, Zero};
+
+        assert_eq!(Mint::default(), 0);
+        assert_eq!(Mint::zero(), 0);
+        assert!(Mint::zero().is_zero());
+        assert!(!Mint::new(1).is_zero());
+        assert_eq!(Mint::one(), 1);
+        assert!(Mint::one().is_one());
+    }
+
+    #[test]
+    fn test_totient() {
+        assert_eq!(Mint::TOTIENT, 1_000_000_006);
+        assert_eq!(StaticModInt::<1>::TOTIENT, 1);
+        assert_eq!(StaticModInt::<12>::TOTIENT, 4);
+        assert_eq!(StaticModInt::<1_000_000_000>::TOTIENT, 400_000_000);
+        assert_eq!(StaticModInt::<2_147_483_647>::TOTIENT, 2_147_483_646);
+    }
+
+    #[test]
+    fn test_pow_u64() {
+        assert_eq!(Mint::new(5).pow_u64(0), 1);
+        assert_eq!(Mint::new(5).pow_u64(3), 125);
+        assert_eq!(Mint::new(5).pow_u64(1_000_000_000_000_000_000), 921_116_510);
+        assert_eq!(Mint::new(0).pow_u64(1 << 40), 0);
+
+        // composite modulus and values not coprime to it
+        type M = StaticModInt<1_000_000_000>;
+        assert_eq!(M::new(2).pow_u64(1_000_000_000_000_000_000), 787_109_376);
+        assert_eq!(M::new(6).pow_u64(1_000_000_000_000_000_000), 787_109_376);
+
+        // the reduced exponent gives the same result as the plain binary method
+        let naive_pow = |a: u64, mut n: u64, m: u64| {
+            let (mut t, mut s) = (1 % m, a % m);
+            while n > 0 {
+                if n & 1 == 1 {
+                    t = t * s % m;
+                }
+                s = s * s % m;
+                n >>= 1;
+            }
+            t
+        };
+        type N = StaticModInt<12>;
+        for a in 0..12 {
+            for n in [
+                1 << 32,
+                (1 << 32) + 1,
+                (1 << 32) + 2,
+                999_999_999_999,
+                u64::MAX,
+            ] {
+                assert_eq!(
+                    N::new(a).pow_u64(n).value as u64,
+                    naive_pow(a as u64, n, 12)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_i64() {
+        assert_eq!(Mint::new(2).pow_i64(10), 1024);
+        assert_eq!(Mint::new(2).pow_i64(-1), 500_000_004);
+        assert_eq!(Mint::new(2).pow_i64(-10) * Mint::new(1024), 1);
+        assert_eq!(
+            Mint::new(3).pow_i64(i64::MIN) * Mint::new(3).pow_u64(1 << 63),
+            1
+        );
+    }
+
+    #[test]
+    fn test_pow_biguint() {
+        use num_bigint::BigUint;
+
+        assert_eq!(Mint::new(2).pow_biguint(&BigUint::from(10u32)), 1024);
+
+        let n = BigUint::from(10u32).pow(30);
+        assert_eq!(Mint::new(2).pow_biguint(&n), 312_267_046);
+
+        type N = StaticModInt<12>;
+        let n = BigUint::from(10u32).pow(40);
+        assert_eq!(N::new(3).pow_biguint(&n), 9);
+        assert_eq!(N::new(2).pow_biguint(&n), 4);
+        assert_eq!(N::new(0).pow_biguint(&n), 0);
+    }
+
+    #[test]
+    fn test_prefix_suffix_products() {
+        let a = [2, 3, 5, 7].map(Mint::new);
+        assert_eq!(prefix_products(&a), [1, 2, 6, 30, 210].map(Mint::new));
+        assert_eq!(suffix_products(&a), [210, 105, 35, 7, 1].map(Mint::new));
+        assert_eq!(prefix_products::<7>(&[]), vec![StaticModInt::new(1)]);
+        assert_eq!(suffix_products::<7>(&[]), vec![StaticModInt::new(1)]);
+    }
+
+    #[test]
+    fn test_batch_inv() {
+        let a = (1..=1000u32)
+            .map(|i| Mint::new(i * 7919))
+            .collect::<Vec<_>>();
+        let expected = a.iter().map(|x| x.inv()).collect::<Vec<_>>();
+        assert_eq!(batch_inv(&a), expected);
+        assert!(batch_inv::<7>(&[]).is_empty());
+
+        // composite modulus
+        type M = StaticModInt<1_000_000_000>;
+        let a = [1, 3, 7, 999_999_999].map(M::new);
+        for (x, y) in a.iter().zip(batch_inv(&a)) {
+            assert_eq!(*x * y, 1);
+        }
+    }
+
+    #[test]
+    fn test_checked_batch_inv() {
+        let a = [1, 2, 3].map(Mint::new);
+        assert_eq!(checked_batch_inv(&a), Ok(batch_inv(&a)));
+        assert_eq!(checked_batch_inv(&[1, 2, 0, 3, 0].map(Mint::new)), Err(2));
+        assert_eq!(checked_batch_inv::<7>(&[]), Ok(vec![]));
+
+        type M = StaticModInt<1_000_000_000>;
+        assert_eq!(checked_batch_inv(&[1, 3, 6, 7].map(M::new)), Err(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "a[2] = 0 is not invertible")]
+    fn test_batch_inv_zero() {
+        batch_inv(&[1, 2, 0, 3].map(Mint::new));
+    }
+
+    #[test]
+    fn test_to_fraction() {
+        assert_eq!(Mint::new(0).to_fraction(), Some((0, 1)));
+        assert_eq!(Mint::new(5).to_fraction(), Some((5, 1)));
+        assert_eq!(Mint::new(500_000_004).to_fraction(), Some((1, 2)));
+        assert_eq!((-Mint::new(1)).to_fraction(), Some((-1, 1)));
+        assert_eq!(
+            (Mint::new(999) / Mint::new(1000)).to_fraction(),
+            Some((999, 1000))
+        );
+        assert_eq!(
+            (Mint::new(3) / -Mint::new(1000)).to_fraction(),
+            Some((-3, 1000))
+        );
+
+        for p in -30..30i64 {
+            for q in 1..30 {
+                let x = Mint::from(p) / Mint::new(q);
+                let (a, b) = x.to_fraction().unwrap();
+                assert_eq!(a * q as i64, p * b as i64);
+            }
+        }
+
+        // no small fraction
+        assert_eq!(Mint::new(314_159_265).to_fraction(), None);
+        assert_eq!(Mint::new(1001).to_fraction(), None);
+        assert_eq!(StaticModInt::<7>::new(3).to_fraction(), None);
+
+        // a small fraction hidden behind a large residue
+        assert_eq!(Mint::new(123_456_789).to_fraction(), Some((-161, 81)));
+        assert_eq!(StaticModInt::<7>::new(6).to_fraction(), Some((-1, 1)));
+
+        // composite modulus: q should be invertible
+        type C = StaticModInt<1_000_000_000>;
+        assert_eq!(C::new(500_000_000).to_fraction(), None);
+        assert_eq!(C::new(250_000_000).to_fraction(), None);
+        assert_eq!(C::new(0).to_fraction(), Some((0, 1)));
+        assert_eq!((C::new(2) / C::new(3)).to_fraction(), Some((2, 3)));
+        assert_eq!((-C::new(1) / C::new(7)).to_fraction(), Some((-1, 7)));
+        assert_eq!(format!("{:#?}", C::new(500_000_000)), "500000000");
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", Mint::new(3)), "StaticModInt { value: 3 }");
+        assert_eq!(format!("{:#?}", Mint::new(3)), "3");
+        assert_eq!(format!("{:#?}", Mint::new(2).inv()), "1/2");
+        assert_eq!(format!("{:#?}", -Mint::new(3).inv()), "-1/3");
+        assert_eq!(format!("{:#?}", Mint::new(314_159_265)), "314159265");
+        assert_eq!(
+            format!("{:#?}", [1, 500_000_004].map(Mint::new)),
+            "[\n    1,\n    1/2,\n]"
+        );
+    }
 }
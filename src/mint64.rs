@@ -0,0 +1,264 @@
+use crate::mint::{inv_mod, parse_mod, ParseModIntError};
+
+// u64 version of StaticModInt (products are computed in u128)
+#[derive(Clone, Copy, Debug)]
+pub struct StaticModInt64<const M: u64> {
+    value: u64,
+}
+
+// modulus should be less than 2^63
+// (a composite modulus is fine, but only values coprime to it have an inverse)
+pub type Mint61 = StaticModInt64<{ (1 << 61) - 1 }>;
+
+impl<const M: u64> std::ops::Add for StaticModInt64<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut d = self.value + rhs.value;
+        if d >= Self::MODULUS {
+            d -= Self::MODULUS;
+        }
+        Self::new(d)
+    }
+}
+
+impl<const M: u64> std::ops::AddAssign for StaticModInt64<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> std::ops::Sub for StaticModInt64<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut d = self.value + Self::MODULUS - rhs.value;
+        if d >= Self::MODULUS {
+            d -= Self::MODULUS;
+        }
+        Self::new(d)
+    }
+}
+
+impl<const M: u64> std::ops::SubAssign for StaticModInt64<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> std::ops::Mul for StaticModInt64<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let d = self.value as u128 * rhs.value as u128 % Self::MODULUS as u128;
+
+        Self::new(d as u64)
+    }
+}
+
+impl<const M: u64> std::ops::MulAssign for StaticModInt64<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> std::ops::Neg for StaticModInt64<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let d = match self.value {
+            0 => 0,
+            _ => Self::MODULUS - self.value,
+        };
+
+        Self::new(d)
+    }
+}
+
+impl<const M: u64> std::fmt::Display for StaticModInt64<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const M: u64> From<usize> for StaticModInt64<M> {
+    fn from(val: usize) -> Self {
+        Self::new(val as u64)
+    }
+}
+
+impl<const M: u64> From<u64> for StaticModInt64<M> {
+    fn from(val: u64) -> Self {
+        Self::new(val)
+    }
+}
+
+impl<const M: u64> From<i64> for StaticModInt64<M> {
+    fn from(val: i64) -> Self {
+        let d = val.rem_euclid(Self::MODULUS as i64);
+
+        Self::new(d as u64)
+    }
+}
+
+impl<const M: u64> std::str::FromStr for StaticModInt64<M> {
+    type Err = ParseModIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_mod(s, Self::MODULUS).map(Self::new)
+    }
+}
+
+impl<const M: u64> From<StaticModInt64<M>> for u64 {
+    fn from(val: StaticModInt64<M>) -> Self {
+        val.value
+    }
+}
+
+impl<const M: u64> PartialEq<Self> for StaticModInt64<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<const M: u64> PartialEq<u64> for StaticModInt64<M> {
+    fn eq(&self, other: &u64) -> bool {
+        self.value == *other
+    }
+}
+
+#[allow(dead_code)]
+impl<const M: u64> StaticModInt64<M> {
+    pub const MODULUS: u64 = M;
+
+    pub fn new(n: u64) -> Self {
+        Self {
+            value: n % Self::MODULUS,
+        }
+    }
+
+    pub fn pow(self, mut n: u64) -> Self {
+        let mut t = Self::new(1);
+        let mut s = self;
+        while n > 0 {
+            if n & 1 == 1 {
+                t *= s;
+            }
+            s *= s;
+            n >>= 1;
+        }
+        t
+    }
+
+    // Returns the inverse, or None when gcd(value, MODULUS) != 1
+    pub fn checked_inv(self) -> Option<Self> {
+        inv_mod(self.value, Self::MODULUS).map(Self::new)
+    }
+
+    pub fn inv(self) -> Self {
+        self.checked_inv()
+            .expect("value is not invertible under the modulus")
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = (1 << 61) - 1;
+
+    #[test]
+    fn test_add_sub() {
+        let l = Mint61::new(P - 10);
+        let r = Mint61::new(15);
+        assert_eq!((l + r).value, 5);
+        assert_eq!((r - l).value, 25);
+        assert_eq!((l - r).value, P - 25);
+
+        let mut m = Mint61::new(P - 1);
+        m += Mint61::new(1);
+        assert_eq!(m.value, 0);
+        m -= Mint61::new(1);
+        assert_eq!(m.value, P - 1);
+    }
+
+    #[test]
+    fn test_mul() {
+        let l = Mint61::new(P - 5);
+        let r = Mint61::new(P - 10);
+        assert_eq!((l * r).value, 50);
+
+        let mut m = Mint61::new(1 << 60);
+        m *= Mint61::new(4);
+        assert_eq!(m.value, 2);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!((-Mint61::new(0)).value, 0);
+        assert_eq!((-Mint61::new(100)).value, P - 100);
+    }
+
+    #[test]
+    fn test_pow_inv() {
+        assert_eq!(Mint61::new(2).pow(61), 1);
+        assert_eq!(Mint61::new(3).pow(P - 1), 1);
+        assert_eq!(Mint61::new(2).inv().value, 1 << 60);
+
+        type Big = StaticModInt64<1_000_000_000_000_000_003>;
+        let a = Big::new(123_456_789_012_345_678);
+        assert_eq!(a * a.inv(), 1);
+    }
+
+    #[test]
+    fn test_checked_inv() {
+        assert_eq!(Mint61::new(0).checked_inv(), None);
+
+        type M = StaticModInt64<{ 1 << 62 }>;
+        assert_eq!(M::new(6).checked_inv(), None);
+        assert_eq!(M::new(3).inv() * M::new(3), 1);
+
+        type N = StaticModInt64<1_000_000_000_000_000_000>;
+        assert_eq!(N::new(10).checked_inv(), None);
+        assert_eq!(
+            N::new(999_999_999_999_999_999).inv(),
+            999_999_999_999_999_999
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Mint61::new(u64::MAX).value, 7);
+        assert_eq!(Mint61::from(P as usize + 3).value, 3);
+        assert_eq!(Mint61::from(P + 3).value, 3);
+        assert_eq!(Mint61::from(-1i64).value, P - 1);
+        assert_eq!(Mint61::from(i64::MIN).value, P - 4);
+        assert_eq!(u64::from(Mint61::new(42)), 42);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format!("{}", Mint61::new(P - 1)), "2305843009213693950");
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        assert!(Mint61::new(10) == Mint61::new(P + 10));
+        assert!(Mint61::new(10) == 10);
+        assert!(Mint61::new(10) != 7);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("-1".parse::<Mint61>(), Ok(Mint61::new(P - 1)));
+        assert_eq!("2305843009213693953".parse::<Mint61>(), Ok(Mint61::new(2)));
+        assert_eq!(
+            "123456789012345678901234567890".parse::<Mint61>(),
+            Ok(Mint61::new(
+                ((123_456_789_012_345_678_901_234_567_890u128) % P as u128) as u64
+            ))
+        );
+        assert!("x".parse::<Mint61>().is_err());
+    }
+}
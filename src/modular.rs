@@ -0,0 +1,345 @@
+use crate::mint::StaticModInt;
+use crate::mint64::StaticModInt64;
+use crate::prime::{pow_mod, prime_factors};
+
+#[allow(dead_code)]
+impl<const M: u32> StaticModInt<M> {
+    // Returns x such that x * x = self by Tonelli-Shanks algorithm
+    // (the smaller one of the two roots, or None when self is a quadratic non-residue)
+    // (modulus should be a prime number)
+    pub fn sqrt(self) -> Option<Self> {
+        if self.value() == 0 || M == 2 {
+            return Some(self);
+        }
+
+        // Euler's criterion
+        if self.pow((M - 1) / 2) != 1 {
+            return None;
+        }
+
+        // M - 1 = q * 2^s (q is odd)
+        let s = (M - 1).trailing_zeros();
+        let q = (M - 1) >> s;
+
+        let mut z = Self::new(2);
+        while z.pow((M - 1) / 2) == 1 {
+            z += Self::new(1);
+        }
+
+        // invariants: r * r = self * t, c^(2^(m - 1)) = -1, t^(2^(m - 1)) = 1
+        let mut m = s;
+        let mut c = z.pow(q);
+        let mut t = self.pow(q);
+        let mut r = self.pow((q + 1) / 2);
+        while t != 1 {
+            let mut i = 0;
+            let mut tt = t;
+            while tt != 1 {
+                tt *= tt;
+                i += 1;
+            }
+
+            let b = c.pow(1 << (m - i - 1));
+            m = i;
+            c = b * b;
+            t *= c;
+            r *= b;
+        }
+
+        if r.value() <= M - r.value() {
+            Some(r)
+        } else {
+            Some(-r)
+        }
+    }
+}
+
+// Returns the smallest x >= 0 such that a^x = b by baby-step giant-step
+// (works for composite moduli too)
+pub fn discrete_log<const M: u32>(a: StaticModInt<M>, b: StaticModInt<M>) -> Option<u64> {
+    discrete_log_mod(a.value() as u64, b.value() as u64, M as u64)
+}
+
+fn discrete_log_mod(a: u64, b: u64, m: u64) -> Option<u64> {
+    if m == 1 {
+        return Some(0);
+    }
+
+    let (mut a, mut b, mut m) = (a % m, b % m, m);
+
+    // divide out the common factors of a and m, so that a becomes invertible
+    // (k * a^(x - add) = b holds for the remaining modulus)
+    let mut k = 1 % m;
+    let mut add = 0;
+    loop {
+        let g = num_integer::gcd(a, m);
+        if g == 1 {
+            break;
+        }
+        if b == k {
+            return Some(add);
+        }
+        if b % g != 0 {
+            return None;
+        }
+        b /= g;
+        m /= g;
+        add += 1;
+        k = k * (a / g) % m;
+        a %= m;
+    }
+
+    let n = (m as f64).sqrt() as u64 + 1;
+
+    // baby steps: b * a^j (later j overwrites, so the largest j is kept)
+    let mut table = std::collections::HashMap::new();
+    let mut cur = b;
+    for j in 0..=n {
+        table.insert(cur, j);
+        cur = cur * a % m;
+    }
+
+    let mut an = 1 % m;
+    for _ in 0..n {
+        an = an * a % m;
+    }
+
+    // giant steps: k * a^(n * i)
+    let mut cur = k;
+    for i in 1..=n {
+        cur = cur * an % m;
+        if let Some(&j) = table.get(&cur) {
+            return Some(n * i - j + add);
+        }
+    }
+
+    None
+}
+
+// Returns the smallest primitive root of prime p
+pub fn primitive_root(p: u64) -> u64 {
+    assert!(p >= 2);
+    if p == 2 {
+        return 1;
+    }
+
+    let factors = prime_factors((p - 1) as usize);
+    (2..)
+        .find(|&g| {
+            factors
+                .keys()
+                .all(|&q| pow_mod(g, (p - 1) / q as u64, p) != 1)
+        })
+        .unwrap()
+}
+
+// Same as primitive_root, but evaluable at compile time for primes less than 2^32
+// (p - 1 is factorized by trial division)
+pub const fn const_primitive_root(p: u32) -> u32 {
+    assert!(p >= 2);
+    if p == 2 {
+        return 1;
+    }
+
+    // prime factors of p - 1
+    let mut factors = [0u64; 32];
+    let mut len = 0;
+    let mut x = (p - 1) as u64;
+    let mut q = 2;
+    while q * q <= x {
+        if x % q == 0 {
+            factors[len] = q;
+            len += 1;
+            while x % q == 0 {
+                x /= q;
+            }
+        }
+        q += 1;
+    }
+    if x > 1 {
+        factors[len] = x;
+        len += 1;
+    }
+
+    let mut g = 2;
+    loop {
+        let mut i = 0;
+        while i < len && pow_mod(g, (p as u64 - 1) / factors[i], p as u64) != 1 {
+            i += 1;
+        }
+        if i == len {
+            return g as u32;
+        }
+        g += 1;
+    }
+}
+
+// Returns the smallest k > 0 such that x^k = 1 (m should be a multiple of the order)
+fn order_dividing<T: Copy + PartialEq>(m: u64, one: T, pow: impl Fn(u64) -> T) -> u64 {
+    let mut k = m;
+    for q in prime_factors(m as usize).into_keys() {
+        let q = q as u64;
+        while k % q == 0 && pow(k / q) == one {
+            k /= q;
+        }
+    }
+    k
+}
+
+impl<const M: u32> StaticModInt<M> {
+    // Returns the multiplicative order, or None when self is not invertible
+    pub fn order(self) -> Option<u64> {
+        self.checked_inv()?;
+        Some(order_dividing(Self::TOTIENT as u64, Self::new(1), |k| {
+            self.pow(k as u32)
+        }))
+    }
+}
+
+impl<const M: u64> StaticModInt64<M> {
+    // Returns the multiplicative order, or None when self is not invertible
+    pub fn order(self) -> Option<u64> {
+        self.checked_inv()?;
+        let totient = prime_factors(M as usize)
+            .into_iter()
+            .fold(M, |phi, (p, _)| phi / p as u64 * (p as u64 - 1));
+        Some(order_dividing(totient, Self::new(1), |k| self.pow(k)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::Mint;
+
+    fn check_sqrt<const P: u32>() {
+        for v in 0..P {
+            let x = StaticModInt::<P>::new(v);
+            let expected = (0..P).find(|&r| r as u64 * r as u64 % P as u64 == v as u64);
+            assert_eq!(x.sqrt().map(|r| r.value()), expected);
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        check_sqrt::<2>();
+        check_sqrt::<3>();
+        check_sqrt::<5>();
+        check_sqrt::<13>();
+        check_sqrt::<17>();
+        check_sqrt::<41>();
+        check_sqrt::<97>();
+        check_sqrt::<257>();
+
+        for v in 1..1000 {
+            let x = Mint::new(v * 7919);
+            if let Some(r) = x.sqrt() {
+                assert_eq!(r * r, x);
+            }
+        }
+        assert_eq!(Mint::new(4).sqrt(), Some(Mint::new(2)));
+        assert_eq!(Mint::new(5).sqrt(), None);
+    }
+
+    fn brute_force_log(a: u64, b: u64, m: u64) -> Option<u64> {
+        let mut cur = 1 % m;
+        for x in 0..=2 * m {
+            if cur == b % m {
+                return Some(x);
+            }
+            cur = cur * a % m;
+        }
+        None
+    }
+
+    #[test]
+    fn test_discrete_log_mod() {
+        for m in 1..=60 {
+            for a in 0..m {
+                for b in 0..m {
+                    assert_eq!(discrete_log_mod(a, b, m), brute_force_log(a, b, m));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(Mint::new(2), Mint::new(1)), Some(0));
+        assert_eq!(discrete_log(Mint::new(2), Mint::new(1024)), Some(10));
+        assert_eq!(discrete_log(Mint::new(0), Mint::new(0)), Some(1));
+        assert_eq!(discrete_log(Mint::new(0), Mint::new(2)), None);
+
+        let a = Mint::new(5);
+        let x = discrete_log(a, Mint::new(123_456_789)).unwrap();
+        assert_eq!(a.pow(x as u32), 123_456_789);
+
+        type M = StaticModInt<1_000_000_000>;
+        assert_eq!(discrete_log(M::new(2), M::new(1 << 20)), Some(20));
+        assert_eq!(discrete_log(M::new(2), M::new(3)), None);
+        assert_eq!(discrete_log(M::new(3), M::new(3).pow(12_345)), Some(12_345));
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        assert_eq!(primitive_root(2), 1);
+        assert_eq!(primitive_root(3), 2);
+        assert_eq!(primitive_root(7), 3);
+        assert_eq!(primitive_root(998_244_353), 3);
+        assert_eq!(primitive_root(754_974_721), 11);
+        assert_eq!(primitive_root(1_000_000_007), 5);
+        assert_eq!(primitive_root((1 << 61) - 1), 37);
+        assert_eq!(primitive_root(9_223_372_036_854_775_783), 3);
+        assert_eq!(primitive_root(999_999_999_999_999_989), 2);
+
+        for p in [
+            2,
+            3,
+            7,
+            167_772_161,
+            469_762_049,
+            998_244_353,
+            1_000_000_007,
+        ] {
+            assert_eq!(const_primitive_root(p) as u64, primitive_root(p as u64));
+        }
+    }
+
+    fn check_order<const M: u32>() {
+        for a in 0..M {
+            let x = StaticModInt::<M>::new(a);
+            let brute_force = (1..=M).find(|&k| x.pow(k) == 1).map(|k| k as u64);
+            assert_eq!(x.order(), brute_force);
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        assert_eq!(Mint::new(1).order(), Some(1));
+        assert_eq!(Mint::new(1_000_000_006).order(), Some(2));
+        assert_eq!(Mint::new(5).order(), Some(1_000_000_006));
+        assert_eq!(Mint::new(2).order(), Some(500_000_003));
+        assert_eq!(Mint::new(0).order(), None);
+        assert_eq!(
+            StaticModInt::<998_244_353>::new(7).order(),
+            Some(499_122_176)
+        );
+
+        // composite modulus
+        type M = StaticModInt<1_000_000_000>;
+        assert_eq!(M::new(3).order(), Some(50_000_000));
+        assert_eq!(M::new(2).order(), None);
+        assert_eq!(StaticModInt::<1>::new(0).order(), Some(1));
+
+        check_order::<2>();
+        check_order::<12>();
+        check_order::<49>();
+        check_order::<97>();
+        check_order::<360>();
+
+        type N = StaticModInt64<999_999_999_999_999_989>;
+        assert_eq!(N::new(2).order(), Some(999_999_999_999_999_988));
+        assert_eq!(N::new(N::MODULUS - 1).order(), Some(2));
+        assert_eq!(StaticModInt64::<{ 1 << 62 }>::new(3).order(), Some(1 << 60));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::mint::{inv_mod, parse_mod, ParseModIntError};
+
+// Modular integers which hold values in Montgomery form (x * R mod M, R = 2^bits),
+// so that multiplication needs no division
+// (modulus should be an odd number less than 2^(bits - 1),
+//  and only values coprime to it have an inverse)
+//
+// value(), Display and comparison with plain integers behave the same as Mint.
+//
+macro_rules! montgomery_modint {
+    ($name:ident, $uint:ty, $wide:ty, $bits:expr) => {
+        #[derive(Clone, Copy)]
+        pub struct $name<const M: $uint> {
+            // Montgomery form of the value
+            x: $uint,
+        }
+
+        impl<const M: $uint> std::ops::Add for $name<M> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                let mut d = self.x + rhs.x;
+                if d >= M {
+                    d -= M;
+                }
+                Self { x: d }
+            }
+        }
+
+        impl<const M: $uint> std::ops::AddAssign for $name<M> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const M: $uint> std::ops::Sub for $name<M> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                let mut d = self.x + M - rhs.x;
+                if d >= M {
+                    d -= M;
+                }
+                Self { x: d }
+            }
+        }
+
+        impl<const M: $uint> std::ops::SubAssign for $name<M> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const M: $uint> std::ops::Mul for $name<M> {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: Self::reduce(self.x as $wide * rhs.x as $wide),
+                }
+            }
+        }
+
+        impl<const M: $uint> std::ops::MulAssign for $name<M> {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<const M: $uint> std::ops::Neg for $name<M> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                let d = match self.x {
+                    0 => 0,
+                    _ => M - self.x,
+                };
+
+                Self { x: d }
+            }
+        }
+
+        impl<const M: $uint> std::fmt::Display for $name<M> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.value())
+            }
+        }
+
+        impl<const M: $uint> std::fmt::Debug for $name<M> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("value", &self.value())
+                    .finish()
+            }
+        }
+
+        impl<const M: $uint> From<usize> for $name<M> {
+            fn from(val: usize) -> Self {
+                Self::new((val as $wide % M as $wide) as $uint)
+            }
+        }
+
+        impl<const M: $uint> std::str::FromStr for $name<M> {
+            type Err = ParseModIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse_mod(s, M as u64).map(|d| Self::new(d as $uint))
+            }
+        }
+
+        impl<const M: $uint> PartialEq<Self> for $name<M> {
+            fn eq(&self, other: &Self) -> bool {
+                self.x == other.x
+            }
+        }
+
+        impl<const M: $uint> PartialEq<$uint> for $name<M> {
+            fn eq(&self, other: &$uint) -> bool {
+                self.value() == *other
+            }
+        }
+
+        #[allow(dead_code)]
+        impl<const M: $uint> $name<M> {
+            pub const MODULUS: $uint = M;
+
+            // -M^-1 mod R (Newton's method doubles the number of correct bits each step)
+            const NEG_INV: $uint = {
+                assert!(M % 2 == 1 && M < 1 << ($bits - 1));
+                let mut inv: $uint = M;
+                let mut i = 0;
+                while i < 6 {
+                    inv = inv.wrapping_mul(<$uint>::wrapping_sub(2, M.wrapping_mul(inv)));
+                    i += 1;
+                }
+                inv.wrapping_neg()
+            };
+
+            // R^2 mod M
+            const R2: $uint = ((<$wide>::MAX % M as $wide + 1) % M as $wide) as $uint;
+
+            // Returns t * R^-1 mod M (t should be less than M * R)
+            fn reduce(t: $wide) -> $uint {
+                let m = (t as $uint).wrapping_mul(Self::NEG_INV);
+                let u = ((t + m as $wide * M as $wide) >> $bits) as $uint;
+                if u >= M {
+                    u - M
+                } else {
+                    u
+                }
+            }
+
+            pub fn new(n: $uint) -> Self {
+                Self {
+                    x: Self::reduce((n % M) as $wide * Self::R2 as $wide),
+                }
+            }
+
+            pub fn pow(self, mut n: $uint) -> Self {
+                let mut t = Self::new(1);
+                let mut s = self;
+                while n > 0 {
+                    if n & 1 == 1 {
+                        t *= s;
+                    }
+                    s *= s;
+                    n >>= 1;
+                }
+                t
+            }
+
+            // Returns the inverse, or None when gcd(value, MODULUS) != 1
+            pub fn checked_inv(self) -> Option<Self> {
+                inv_mod(self.value() as u64, M as u64).map(|x| Self::new(x as $uint))
+            }
+
+            pub fn inv(self) -> Self {
+                self.checked_inv()
+                    .expect("value is not invertible under the modulus")
+            }
+
+            pub fn value(&self) -> $uint {
+                Self::reduce(self.x as $wide)
+            }
+        }
+    };
+}
+
+montgomery_modint!(MontgomeryModInt, u32, u64, 32);
+montgomery_modint!(MontgomeryModInt64, u64, u128, 64);
+
+pub type MontgomeryMint = MontgomeryModInt<1_000_000_007>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::Mint;
+
+    #[test]
+    fn test_same_as_mint() {
+        let mut m = Mint::new(1);
+        let mut g = MontgomeryMint::new(1);
+        for i in 1..1000 {
+            m = m * Mint::new(i * 7919) + Mint::new(i) - Mint::new(i * i);
+            g = g * MontgomeryMint::new(i * 7919) + MontgomeryMint::new(i)
+                - MontgomeryMint::new(i * i);
+            assert_eq!(m.value(), g.value());
+        }
+        assert_eq!(m.pow(123_456).value(), g.pow(123_456).value());
+        assert_eq!(m.inv().value(), g.inv().value());
+        assert_eq!(format!("{}", m), format!("{}", g));
+        assert_eq!((-m).value(), (-g).value());
+    }
+
+    #[test]
+    fn test_observable() {
+        assert_eq!(MontgomeryMint::new(10), 10);
+        assert_eq!(MontgomeryMint::new(std::u32::MAX), 294_967_267);
+        assert_eq!(
+            MontgomeryMint::from(std::u32::MAX as usize + 1),
+            294_967_268
+        );
+        assert_eq!(MontgomeryMint::new(0), 0);
+        assert_eq!(-MontgomeryMint::new(0), 0);
+        assert_eq!(
+            format!("{}", MontgomeryMint::new(1_000_000_006)),
+            "1000000006"
+        );
+        assert_eq!(
+            format!("{:?}", MontgomeryMint::new(3)),
+            "MontgomeryModInt { value: 3 }"
+        );
+        assert_eq!(MontgomeryModInt::<998_244_353>::new(2).inv(), 499_122_177);
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut g = MontgomeryMint::new(10);
+        g += MontgomeryMint::new(5);
+        g -= MontgomeryMint::new(3);
+        g *= MontgomeryMint::new(2);
+        assert_eq!(g, MontgomeryMint::new(24));
+    }
+
+    #[test]
+    fn test_64() {
+        type M61 = MontgomeryModInt64<{ (1 << 61) - 1 }>;
+        const P: u128 = (1 << 61) - 1;
+
+        let mut g = M61::new(1);
+        let mut e: u128 = 1;
+        for i in 1..1000u64 {
+            let a = i.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            g = g * M61::new(a) + M61::new(i);
+            e = (e * (a as u128 % P) + i as u128) % P;
+            assert_eq!(g.value() as u128, e);
+        }
+        assert_eq!(g * g.inv(), 1);
+        assert_eq!(M61::new(2).pow((1 << 61) - 2), 1);
+
+        type Big = MontgomeryModInt64<1_000_000_000_000_000_003>;
+        let a = Big::new(999_999_999_999_999_999);
+        assert_eq!(a * a, 16);
+        assert_eq!(
+            a - Big::new(1_000_000_000_000_000_002),
+            1_000_000_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("-1".parse::<MontgomeryMint>().unwrap(), 1_000_000_006);
+        assert_eq!(
+            "-1".parse::<MontgomeryModInt64<1_000_000_000_000_000_003>>()
+                .unwrap(),
+            1_000_000_000_000_000_002
+        );
+        assert!("1.5".parse::<MontgomeryMint>().is_err());
+    }
+
+    #[test]
+    fn test_composite_modulus() {
+        // 3^19
+        type M = MontgomeryModInt<1_162_261_467>;
+        let x = M::new(2);
+        assert_eq!(x * x.inv(), 1);
+        assert_eq!(M::new(3).checked_inv(), None);
+        for a in [1, 2, 4, 5, 7, 1_162_261_466] {
+            assert_eq!(M::new(a) * M::new(a).inv(), 1);
+        }
+
+        type N = MontgomeryModInt64<{ 3u64.pow(39) }>;
+        assert_eq!(N::new(10).inv() * N::new(10), 1);
+        assert_eq!(N::new(6).checked_inv(), None);
+    }
+}
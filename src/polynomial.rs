@@ -0,0 +1,188 @@
+use crate::fps::Fps;
+use crate::mint::StaticModInt;
+use crate::precalc::Precalc;
+
+// Subproduct tree of (x - xs[i])
+// (node i has children 2i and 2i + 1, leaves start from index size)
+struct SubproductTree<const M: u32> {
+    size: usize,
+    nodes: Vec<Fps<M>>,
+}
+
+impl<const M: u32> SubproductTree<M> {
+    fn new(xs: &[StaticModInt<M>]) -> Self {
+        let size = xs.len().next_power_of_two();
+        let mut nodes = vec![Fps(vec![StaticModInt::new(1)]); 2 * size];
+        for (i, x) in xs.iter().enumerate() {
+            nodes[size + i] = Fps(vec![-*x, StaticModInt::new(1)]);
+        }
+        for i in (1..size).rev() {
+            nodes[i] = &nodes[2 * i] * &nodes[2 * i + 1];
+        }
+        Self { size, nodes }
+    }
+
+    // Returns f(xs[i]) for each leaf by taking remainders from the root to the leaves
+    fn evaluate(&self, f: &Fps<M>, n: usize) -> Vec<StaticModInt<M>> {
+        let mut rem = vec![Fps(vec![]); 2 * self.size];
+        rem[1] = f.div_rem(&self.nodes[1]).1;
+        for i in 2..self.size + n {
+            rem[i] = rem[i / 2].div_rem(&self.nodes[i]).1;
+        }
+
+        (0..n)
+            .map(|i| {
+                rem[self.size + i]
+                    .0
+                    .first()
+                    .copied()
+                    .unwrap_or(StaticModInt::new(0))
+            })
+            .collect()
+    }
+}
+
+// Returns f(xs[0]), f(xs[1]), ... in O(n log^2 n)
+pub fn evaluate_multipoint<const M: u32>(
+    f: &Fps<M>,
+    xs: &[StaticModInt<M>],
+) -> Vec<StaticModInt<M>> {
+    if xs.is_empty() {
+        return vec![];
+    }
+    SubproductTree::new(xs).evaluate(f, xs.len())
+}
+
+// Returns the polynomial f of degree less than n such that f(xs[i]) = ys[i] in O(n log^2 n)
+// (xs should be distinct)
+pub fn interpolate<const M: u32>(xs: &[StaticModInt<M>], ys: &[StaticModInt<M>]) -> Fps<M> {
+    assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+    if n == 0 {
+        return Fps(vec![]);
+    }
+
+    // f = sum of ys[i] / w[i] * prod (x - xs[j]) (j != i), where w[i] = P'(xs[i])
+    let tree = SubproductTree::new(xs);
+    let w = tree.evaluate(&tree.nodes[1].derivative(), n);
+
+    let size = tree.size;
+    let mut sums = vec![Fps(vec![]); 2 * size];
+    for i in 0..n {
+        sums[size + i] = Fps(vec![ys[i] * w[i].inv()]);
+    }
+    for i in (1..size).rev() {
+        sums[i] =
+            &(&sums[2 * i] * &tree.nodes[2 * i + 1]) + &(&sums[2 * i + 1] * &tree.nodes[2 * i]);
+    }
+
+    sums[1].prefix(n)
+}
+
+// Returns f(x) for the polynomial f of degree at most n with f(i) = ys[i] (i = 0, ..., n) in O(n)
+// (pc should be built for n or more, and M should be larger than n)
+pub fn interpolate_consecutive<const M: u32>(
+    ys: &[StaticModInt<M>],
+    x: StaticModInt<M>,
+    pc: &Precalc<M>,
+) -> StaticModInt<M> {
+    let n = match ys.len() {
+        0 => return StaticModInt::new(0),
+        len => len - 1,
+    };
+
+    // prefix[i] = (x - 0) ... (x - (i - 1)), suffix[i] = (x - (i + 1)) ... (x - n)
+    let mut prefix = vec![StaticModInt::new(1); n + 1];
+    for i in 0..n {
+        prefix[i + 1] = prefix[i] * (x - StaticModInt::from(i));
+    }
+    let mut suffix = vec![StaticModInt::new(1); n + 1];
+    for i in (0..n).rev() {
+        suffix[i] = suffix[i + 1] * (x - StaticModInt::from(i + 1));
+    }
+
+    // prod (i - j) (j != i) = i! * (n - i)! * (-1)^(n - i)
+    let mut ans = StaticModInt::new(0);
+    for i in 0..=n {
+        let term = ys[i] * prefix[i] * suffix[i] * pc.ifact(i) * pc.ifact(n - i);
+        if (n - i) % 2 == 0 {
+            ans += term;
+        } else {
+            ans -= term;
+        }
+    }
+    ans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::sequence;
+
+    fn check<const M: u32>() {
+        for (n, m) in [(1, 1), (1, 5), (5, 1), (10, 7), (100, 300), (500, 500)] {
+            let f = Fps(sequence::<M>(n, 1));
+            let xs = sequence::<M>(m, 2);
+            let expected = xs.iter().map(|x| f.evaluate(*x)).collect::<Vec<_>>();
+            assert_eq!(evaluate_multipoint(&f, &xs), expected);
+        }
+        assert!(evaluate_multipoint(&Fps::<M>(vec![]), &[]).is_empty());
+        assert_eq!(
+            evaluate_multipoint(&Fps::<M>(vec![]), &[StaticModInt::new(3)]),
+            vec![StaticModInt::new(0)]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_multipoint() {
+        check::<998_244_353>();
+        check::<1_000_000_007>();
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // f(x) = x^2 + 1
+        let xs = [0, 1, 2].map(StaticModInt::<998_244_353>::new);
+        let ys = [1, 2, 5].map(StaticModInt::new);
+        assert_eq!(
+            interpolate(&xs, &ys),
+            Fps(vec![1, 0, 1].into_iter().map(StaticModInt::new).collect())
+        );
+
+        for n in [1, 2, 3, 100, 300] {
+            let xs = (0..n as u32)
+                .map(|i| StaticModInt::<1_000_000_007>::new(i * i + 7 * i + 3))
+                .collect::<Vec<_>>();
+            let ys = sequence(n, 5);
+            let f = interpolate(&xs, &ys);
+            assert_eq!(f.len(), n);
+            assert_eq!(evaluate_multipoint(&f, &xs), ys);
+        }
+        assert!(interpolate::<998_244_353>(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_interpolate_consecutive() {
+        let pc = Precalc::<998_244_353>::new(100);
+        for n in [1, 2, 10, 100] {
+            let f = Fps(sequence::<998_244_353>(n, 3));
+            let ys = (0..n)
+                .map(|i| f.evaluate(StaticModInt::from(i)))
+                .collect::<Vec<_>>();
+            for x in [0, 5, 99, 123_456_789] {
+                let x = StaticModInt::new(x);
+                assert_eq!(interpolate_consecutive(&ys, x, &pc), f.evaluate(x));
+            }
+        }
+
+        // sum of i^2 (i = 0, ..., x)
+        let pc: Precalc = Precalc::new(3);
+        let ys = [0, 1, 5, 14].map(StaticModInt::new);
+        let x = StaticModInt::new(1_000_000);
+        assert_eq!(
+            interpolate_consecutive(&ys, x, &pc),
+            StaticModInt::from(1_000_000u64 * 1_000_001 * 2_000_001 / 6)
+        );
+        assert_eq!(interpolate_consecutive(&[], x, &pc), 0);
+    }
+}
@@ -1,66 +1,186 @@
-use crate::mint::Mint;
+use crate::mint::{checked_batch_inv, StaticModInt, DEFAULT_MODULUS};
 
 // Calculate combination and its elements as mod value
-// (mod is defined as Mint::MODULUS const value)
-pub struct Precalc {
-    inv: Vec<Mint>,
-    fact: Vec<Mint>,
-    ifact: Vec<Mint>,
+// (mod is given as M, and defaults to the modulus of Mint)
+//
+// Tables can be extended later by ensure(n),
+// so new(n) only decides the initial size.
+pub struct Precalc<const M: u32 = DEFAULT_MODULUS> {
+    inv: Vec<StaticModInt<M>>,
+    fact: Vec<StaticModInt<M>>,
+    ifact: Vec<StaticModInt<M>>,
+}
+
+impl<const M: u32> Default for Precalc<M> {
+    fn default() -> Self {
+        Self::new(0)
+    }
 }
 
 #[allow(dead_code)]
-impl Precalc {
+impl<const M: u32> Precalc<M> {
+    // (n should be less than M, since otherwise some of 1, ..., n are not invertible)
     pub fn new(n: usize) -> Self {
-        let mut inv = vec![Mint::new(1); n + 1];
-        let mut fact = vec![Mint::new(1); n + 1];
-        let mut ifact = vec![Mint::new(1); n + 1];
-
-        for i in 2..=n {
-            fact[i] = fact[i - 1] * Mint::new(i as u32);
-        }
-
-        ifact[n] = fact[n].inv();
+        let one = StaticModInt::new(1);
+        let mut pc = Self {
+            inv: vec![one],
+            fact: vec![one],
+            ifact: vec![one],
+        };
+        pc.ensure(n);
+        pc
+    }
 
-        if n > 0 {
-            inv[n] = ifact[n] * fact[n - 1];
+    // Extends the tables to cover n at least
+    // (n should be less than M, and the size is doubled at a time, but it does not reach M)
+    pub fn ensure(&mut self, n: usize) {
+        let len = self.fact.len();
+        if n < len {
+            return;
         }
+        assert!(
+            n < M as usize,
+            "tables cannot cover {} under the modulus {}",
+            n,
+            M
+        );
 
-        for i in (1..n).rev() {
-            ifact[i] = ifact[i + 1] * Mint::new((i + 1) as u32);
-            inv[i] = ifact[i] * fact[i - 1];
+        let n = std::cmp::max(n, std::cmp::min(2 * len, M as usize - 1));
+        let nums = (len..=n).map(StaticModInt::from).collect::<Vec<_>>();
+        let invs = checked_batch_inv(&nums)
+            .unwrap_or_else(|i| panic!("{} is not invertible under the modulus {}", len + i, M));
+        for (x, y) in nums.iter().zip(invs) {
+            let fact = *self.fact.last().unwrap() * *x;
+            let ifact = *self.ifact.last().unwrap() * y;
+            self.fact.push(fact);
+            self.ifact.push(ifact);
+            self.inv.push(y);
         }
+    }
 
-        Self { inv, fact, ifact }
+    // Returns the size of the tables (indices less than it are computed already)
+    pub fn size(&self) -> usize {
+        self.fact.len()
     }
 
-    pub fn inv(&self, n: usize) -> Mint {
+    pub fn inv(&self, n: usize) -> StaticModInt<M> {
         assert!(n > 0);
         self.inv[n]
     }
 
-    pub fn fact(&self, n: usize) -> Mint {
+    pub fn fact(&self, n: usize) -> StaticModInt<M> {
         self.fact[n]
     }
 
-    pub fn ifact(&self, n: usize) -> Mint {
+    pub fn ifact(&self, n: usize) -> StaticModInt<M> {
         self.ifact[n]
     }
 
-    pub fn comb(&self, n: usize, k: usize) -> Mint {
+    pub fn comb(&self, n: usize, k: usize) -> StaticModInt<M> {
+        if k > n {
+            return StaticModInt::new(0);
+        }
+        self.fact(n) * self.ifact(k) * self.ifact(n - k)
+    }
+
+    // comb for signed arguments (0 when n < 0, k < 0 or k > n)
+    pub fn comb_signed(&self, n: i64, k: i64) -> StaticModInt<M> {
+        if n < 0 || k < 0 {
+            return StaticModInt::new(0);
+        }
+        self.comb(n as usize, k as usize)
+    }
+
+    // comb(n, k) for huge n by Lucas' theorem, using the tables up to M - 1
+    // (M should be a small prime number, and the tables should cover M - 1)
+    pub fn comb_lucas(&self, mut n: u64, mut k: u64) -> StaticModInt<M> {
+        let m = M as u64;
+        let mut ret = StaticModInt::new(1);
+        while k > 0 {
+            ret *= self.comb((n % m) as usize, (k % m) as usize);
+            n /= m;
+            k /= m;
+        }
+        ret
+    }
+
+    // comb(n, k) = n (n - 1) ... (n - k + 1) / k! for huge n in O(k)
+    // (the tables should cover k)
+    pub fn comb_small_k(&self, n: u64, k: usize) -> StaticModInt<M> {
+        if k as u64 > n {
+            return StaticModInt::new(0);
+        }
+        let numer = (0..k as u64)
+            .map(|i| StaticModInt::from(n - i))
+            .product::<StaticModInt<M>>();
+        numer * self.ifact(k)
+    }
+
+    // Number of ways to arrange k of n distinct items in order
+    pub fn perm(&self, n: usize, k: usize) -> StaticModInt<M> {
         if k > n {
-            return Mint::new(0);
+            return StaticModInt::new(0);
+        }
+        self.fact(n) * self.ifact(n - k)
+    }
+
+    // Number of ways to choose k of n kinds of items with repetition (= comb(n + k - 1, k))
+    pub fn multichoose(&self, n: usize, k: usize) -> StaticModInt<M> {
+        if n == 0 {
+            return StaticModInt::new((k == 0) as u32);
         }
-        self.fact[n] * self.ifact[k] * self.ifact[n - k]
+        self.comb(n + k - 1, k)
+    }
+
+    // (2n)! / (n! (n + 1)!)
+    pub fn catalan(&self, n: usize) -> StaticModInt<M> {
+        self.fact(2 * n) * self.ifact(n) * self.ifact(n + 1)
+    }
+
+    // (k[0] + k[1] + ...)! / (k[0]! k[1]! ...)
+    pub fn multinomial(&self, k: &[usize]) -> StaticModInt<M> {
+        let n = k.iter().sum();
+        k.iter().fold(self.fact(n), |acc, &x| acc * self.ifact(x))
+    }
+
+    // Number of permutations of n items without fixed points
+    // (n! * sum of (-1)^i / i! in O(n))
+    pub fn derangement(&self, n: usize) -> StaticModInt<M> {
+        let mut sum = StaticModInt::new(0);
+        for i in 0..=n {
+            if i % 2 == 0 {
+                sum += self.ifact(i);
+            } else {
+                sum -= self.ifact(i);
+            }
+        }
+        self.fact(n) * sum
+    }
+
+    // Number of monotone lattice paths from (0, 0) to (x, y)
+    pub fn lattice_paths(&self, x: usize, y: usize) -> StaticModInt<M> {
+        self.comb(x + y, x)
+    }
+
+    // Number of monotone lattice paths from (0, 0) to (x, y) which never go above y = x,
+    // i.e. sequences of x votes for A and y votes for B where A is never behind
+    // (reflection principle: comb(x + y, y) - comb(x + y, y - 1))
+    pub fn ballot(&self, x: usize, y: usize) -> StaticModInt<M> {
+        if y > x {
+            return StaticModInt::new(0);
+        }
+        self.comb(x + y, y) - self.comb_signed((x + y) as i64, y as i64 - 1)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mint::Mint;
 
     #[test]
     fn test_fact() {
-        let pc = Precalc::new(20);
+        let pc: Precalc = Precalc::new(20);
         assert_eq!(pc.fact(0), 1);
         assert_eq!(pc.fact(1), 1);
         assert_eq!(pc.fact(2), 2);
@@ -71,7 +191,7 @@ mod tests {
 
     #[test]
     fn test_inv() {
-        let pc = Precalc::new(20);
+        let pc: Precalc = Precalc::new(20);
         assert_eq!(pc.inv(1), 1);
         assert_eq!(pc.inv(2), 500_000_004);
         assert_eq!(pc.inv(3), 333_333_336);
@@ -80,7 +200,7 @@ mod tests {
 
     #[test]
     fn test_ifact() {
-        let pc = Precalc::new(20);
+        let pc: Precalc = Precalc::new(20);
         assert_eq!(pc.ifact(0), 1);
         assert_eq!(pc.ifact(1), 1);
         assert_eq!(pc.ifact(2), 500_000_004);
@@ -90,7 +210,7 @@ mod tests {
 
     #[test]
     fn test_comb() {
-        let pc = Precalc::new(20);
+        let pc: Precalc = Precalc::new(20);
         assert_eq!(pc.comb(0, 0), 1);
         assert_eq!(pc.comb(1, 0), 1);
         assert_eq!(pc.comb(1, 1), 1);
@@ -104,4 +224,189 @@ mod tests {
         // n < k
         assert_eq!(pc.comb(2, 5), 0);
     }
+
+    #[test]
+    fn test_other_modulus() {
+        let pc = Precalc::<998_244_353>::new(20);
+        assert_eq!(pc.inv(2), 499_122_177);
+        assert_eq!(pc.fact(20), 401_576_539);
+        assert_eq!(pc.comb(20, 10), 184_756);
+    }
+
+    #[test]
+    fn test_large() {
+        let pc: Precalc = Precalc::new(1_000_000);
+        assert_eq!(pc.fact(1_000_000), 641_102_369);
+        for i in [1, 2, 999, 1_000_000] {
+            assert_eq!(pc.inv(i) * StaticModInt::from(i), 1);
+            assert_eq!(pc.fact(i) * pc.ifact(i), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "tables cannot cover 7 under the modulus 7")]
+    fn test_too_large() {
+        Precalc::<7>::new(7);
+    }
+
+    #[test]
+    #[should_panic(expected = "tables cannot cover 7 under the modulus 7")]
+    fn test_ensure_too_large() {
+        let mut pc = Precalc::<7>::new(0);
+        pc.ensure(7);
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut pc: Precalc = Precalc::default();
+        assert_eq!(pc.size(), 1);
+        pc.ensure(20);
+        assert_eq!(pc.size(), 21);
+        assert_eq!(pc.fact(20), 146_326_063);
+        pc.ensure(100);
+        assert_eq!(pc.size(), 101);
+        assert_eq!(pc.comb(100, 50), 538_992_043);
+
+        // doubled
+        pc.ensure(102);
+        assert_eq!(pc.size(), 203);
+        assert_eq!(pc.inv(102), Mint::new(102).inv());
+        pc.ensure(1000);
+
+        let fixed: Precalc = Precalc::new(1000);
+        for i in 1..1000 {
+            assert_eq!(pc.fact(i), fixed.fact(i));
+            assert_eq!(pc.ifact(i), fixed.ifact(i));
+            assert_eq!(pc.inv(i), fixed.inv(i));
+        }
+    }
+
+    #[test]
+    fn test_static() {
+        static PC: std::sync::OnceLock<Precalc> = std::sync::OnceLock::new();
+        let pc = PC.get_or_init(|| Precalc::new(100));
+        assert_eq!(pc.comb(100, 50), 538_992_043);
+    }
+
+    #[test]
+    fn test_small_modulus() {
+        let mut pc = Precalc::<7>::new(0);
+        pc.ensure(4);
+        assert_eq!(pc.size(), 5);
+
+        // doubled, but capped at M - 1
+        pc.ensure(5);
+        assert_eq!(pc.size(), 7);
+        assert_eq!(pc.comb(6, 3), 6);
+    }
+
+    #[test]
+    fn test_comb_signed() {
+        let pc: Precalc = Precalc::new(1000);
+        assert_eq!(pc.comb_signed(5, 2), 10);
+        assert_eq!(pc.comb_signed(5, 6), 0);
+        assert_eq!(pc.comb_signed(-1, 0), 0);
+        assert_eq!(pc.comb_signed(5, -1), 0);
+    }
+
+    #[test]
+    fn test_perm_multichoose() {
+        let pc: Precalc = Precalc::new(1000);
+        assert_eq!(pc.perm(5, 0), 1);
+        assert_eq!(pc.perm(5, 2), 20);
+        assert_eq!(pc.perm(5, 5), 120);
+        assert_eq!(pc.perm(5, 6), 0);
+
+        assert_eq!(pc.multichoose(3, 2), 6);
+        assert_eq!(pc.multichoose(1, 10), 1);
+        assert_eq!(pc.multichoose(0, 0), 1);
+        assert_eq!(pc.multichoose(0, 3), 0);
+        assert_eq!(pc.multichoose(5, 0), 1);
+    }
+
+    #[test]
+    fn test_catalan_ballot() {
+        let pc: Precalc = Precalc::new(1000);
+        let catalan = [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862];
+        for (n, c) in catalan.into_iter().enumerate() {
+            assert_eq!(pc.catalan(n), c);
+            assert_eq!(pc.ballot(n, n), c);
+        }
+
+        assert_eq!(pc.lattice_paths(2, 3), 10);
+        assert_eq!(pc.lattice_paths(0, 0), 1);
+        assert_eq!(pc.ballot(3, 0), 1);
+        assert_eq!(pc.ballot(3, 2), 5);
+        assert_eq!(pc.ballot(2, 3), 0);
+
+        // brute force over all vote sequences
+        for x in 0..8 {
+            for y in 0..8 {
+                let count = (0..1u32 << (x + y))
+                    .filter(|s| s.count_ones() == y as u32)
+                    .filter(|s| {
+                        (0..x + y)
+                            .scan(0i32, |d, i| {
+                                *d += if s >> i & 1 == 1 { -1 } else { 1 };
+                                Some(*d)
+                            })
+                            .all(|d| d >= 0)
+                    })
+                    .count();
+                assert_eq!(pc.ballot(x, y), count as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multinomial() {
+        let pc: Precalc = Precalc::new(1000);
+        assert_eq!(pc.multinomial(&[]), 1);
+        assert_eq!(pc.multinomial(&[3]), 1);
+        assert_eq!(pc.multinomial(&[2, 3]), pc.comb(5, 2));
+        // MISSISSIPPI
+        assert_eq!(pc.multinomial(&[1, 4, 4, 2]), 34_650);
+    }
+
+    #[test]
+    fn test_derangement() {
+        let pc: Precalc = Precalc::new(1000);
+        let expected = [1, 0, 1, 2, 9, 44, 265, 1854, 14_833, 133_496];
+        for (n, d) in expected.into_iter().enumerate() {
+            assert_eq!(pc.derangement(n), d);
+        }
+    }
+
+    #[test]
+    fn test_comb_lucas() {
+        let pc = Precalc::<7>::new(6);
+        for n in 0..100u128 {
+            for k in 0..=n {
+                let expected = (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1));
+                assert_eq!(pc.comb_lucas(n as u64, k as u64), (expected % 7) as u32);
+            }
+        }
+
+        let pc = Precalc::<1009>::new(1008);
+        assert_eq!(pc.comb_lucas(987_654_321_987_654_321, 123_456_789), 547);
+        assert_eq!(pc.comb_lucas(5, 6), 0);
+
+        let pc = Precalc::<2>::new(1);
+        assert_eq!(pc.comb_lucas(u64::MAX, 123_456_789), 1);
+        assert_eq!(pc.comb_lucas(1 << 40, 1), 0);
+    }
+
+    #[test]
+    fn test_comb_small_k() {
+        let pc: Precalc = Precalc::new(1000);
+        assert_eq!(pc.comb_small_k(1_000_000_000_000_000_000, 3), 18_424);
+        assert_eq!(pc.comb_small_k(987_654_321_987_654_321, 1000), 475_646_825);
+        assert_eq!(pc.comb_small_k(10, 11), 0);
+        assert_eq!(pc.comb_small_k(0, 0), 1);
+        for n in 0..30 {
+            for k in 0..30 {
+                assert_eq!(pc.comb_small_k(n as u64, k), pc.comb(n, k));
+            }
+        }
+    }
 }
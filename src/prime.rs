@@ -22,7 +22,12 @@ pub fn primes(n: usize) -> Vec<usize> {
 }
 
 // Returns prime factors of n
+// (by trial division for small n, and by Pollard's rho algorithm for large n)
 pub fn prime_factors(n: usize) -> std::collections::HashMap<usize, usize> {
+    if n as u64 >= 1 << 40 {
+        return prime_factors_rho(n as u64);
+    }
+
     let mut n = n;
     let mut pf = std::collections::HashMap::new();
     let ps = primes((n as f64).sqrt() as usize + 2);
@@ -47,6 +52,115 @@ pub fn prime_factors(n: usize) -> std::collections::HashMap<usize, usize> {
     pf
 }
 
+fn prime_factors_rho(n: u64) -> std::collections::HashMap<usize, usize> {
+    let mut pf = std::collections::HashMap::new();
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            *pf.entry(m as usize).or_insert(0) += 1;
+            continue;
+        }
+        let d = pollard_rho(m);
+        stack.push(d);
+        stack.push(m / d);
+    }
+    pf
+}
+
+// Returns x^n mod m (usable in constant expressions)
+pub const fn pow_mod(mut x: u64, mut n: u64, m: u64) -> u64 {
+    let mut r = 1 % m;
+    x %= m;
+    while n > 0 {
+        if n & 1 == 1 {
+            r = (r as u128 * x as u128 % m as u128) as u64;
+        }
+        x = (x as u128 * x as u128 % m as u128) as u64;
+        n >>= 1;
+    }
+    r
+}
+
+// Deterministic Miller-Rabin primality test for n < 2^64
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+
+    // n - 1 = d * 2^s (d is odd)
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    [2, 325, 9375, 28178, 450_775, 9_780_504, 1_795_265_022]
+        .iter()
+        .all(|&a| {
+            let mut x = pow_mod(a, d, n);
+            if x == 0 || x == 1 || x == n - 1 {
+                return true;
+            }
+            for _ in 1..s {
+                x = (x as u128 * x as u128 % n as u128) as u64;
+                if x == n - 1 {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+// Returns a non-trivial factor of composite n by Pollard's rho algorithm (Brent's variant)
+fn pollard_rho(n: u64) -> u64 {
+    if n % 2 == 0 {
+        return 2;
+    }
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+
+    // products of 128 differences are taken before each gcd
+    const STEP: u64 = 128;
+    for c in 1.. {
+        let f = |x: u64| ((mul(x, x) as u128 + c as u128) % n as u128) as u64;
+        let (mut x, mut y, mut ys) = (0, 2, 0);
+        let (mut g, mut q, mut r) = (1, 1, 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..std::cmp::min(STEP, r - k) {
+                    y = f(y);
+                    q = mul(q, x.abs_diff(y));
+                }
+                g = num_integer::gcd(q, n);
+                k += STEP;
+            }
+            r <<= 1;
+        }
+
+        // the batch overshot, so step back one by one
+        if g == n {
+            g = 1;
+            while g == 1 {
+                ys = f(ys);
+                g = num_integer::gcd(x.abs_diff(ys), n);
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,4 +195,59 @@ mod tests {
             assert_eq!(pf, ans);
         });
     }
+
+    #[test]
+    fn test_is_prime() {
+        let ps = primes(10_000);
+        for n in 0..=10_000 {
+            assert_eq!(is_prime(n as u64), ps.binary_search(&n).is_ok());
+        }
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime((1 << 61) - 1));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn test_prime_factors_large() {
+        vec![
+            (
+                u64::MAX,
+                vec![
+                    (3u64, 1),
+                    (5, 1),
+                    (17, 1),
+                    (257, 1),
+                    (641, 1),
+                    (65537, 1),
+                    (6_700_417, 1),
+                ],
+            ),
+            (1 << 60, vec![(2, 60)]),
+            (
+                4_294_967_291 * 4_294_967_279,
+                vec![(4_294_967_279, 1), (4_294_967_291, 1)],
+            ),
+            (1_000_000_007 * 1_000_000_007, vec![(1_000_000_007, 2)]),
+            (
+                999_999_999_999_999_989 * 2,
+                vec![(2, 1), (999_999_999_999_999_989, 1)],
+            ),
+            (
+                1_000_000_007 * 998_244_353 * 3,
+                vec![(3, 1), (998_244_353, 1), (1_000_000_007, 1)],
+            ),
+        ]
+        .into_iter()
+        .for_each(|(n, factors)| {
+            let pf = prime_factors(n as usize);
+            let mut ans = std::collections::HashMap::new();
+            for (p, k) in factors {
+                ans.insert(p as usize, k);
+            }
+            assert_eq!(pf, ans);
+        });
+    }
 }
@@ -0,0 +1,260 @@
+use num_traits::{One, Zero};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Element a + b * sqrt(d) of the quadratic extension T[sqrt(d)] (e.g. T = Mint)
+// (d should be a quadratic non-residue to make it a field, and operands should share d)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuadExt<T> {
+    pub a: T,
+    pub b: T,
+    d: T,
+}
+
+impl<T> Add for QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        debug_assert!(self.d == rhs.d);
+        Self {
+            a: self.a + rhs.a,
+            b: self.b + rhs.b,
+            d: self.d,
+        }
+    }
+}
+
+impl<T> std::ops::AddAssign for QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> Sub for QuadExt<T>
+where
+    T: Copy + PartialEq + Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        debug_assert!(self.d == rhs.d);
+        Self {
+            a: self.a - rhs.a,
+            b: self.b - rhs.b,
+            d: self.d,
+        }
+    }
+}
+
+impl<T> std::ops::SubAssign for QuadExt<T>
+where
+    T: Copy + PartialEq + Sub<Output = T>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> Mul for QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    // (a + b sqrt(d)) (c + e sqrt(d)) = (ac + bed) + (ae + bc) sqrt(d)
+    fn mul(self, rhs: Self) -> Self {
+        debug_assert!(self.d == rhs.d);
+        Self {
+            a: self.a * rhs.a + self.b * rhs.b * self.d,
+            b: self.a * rhs.b + self.b * rhs.a,
+            d: self.d,
+        }
+    }
+}
+
+impl<T> std::ops::MulAssign for QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> Div for QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    T: Div<Output = T> + Neg<Output = T>,
+{
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<T> std::ops::DivAssign for QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    T: Div<Output = T> + Neg<Output = T>,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T> Neg for QuadExt<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            a: -self.a,
+            b: -self.b,
+            d: self.d,
+        }
+    }
+}
+
+impl<T: Copy> QuadExt<T> {
+    pub fn new(a: T, b: T, d: T) -> Self {
+        Self { a, b, d }
+    }
+
+    pub fn d(&self) -> T {
+        self.d
+    }
+}
+
+impl<T> QuadExt<T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Mul<Output = T> + Zero + One,
+{
+    // a as an element of T[sqrt(d)]
+    pub fn from_base(a: T, d: T) -> Self {
+        Self::new(a, T::zero(), d)
+    }
+
+    pub fn pow(self, mut n: u64) -> Self {
+        let mut t = Self::from_base(T::one(), self.d);
+        let mut s = self;
+        while n > 0 {
+            if n & 1 == 1 {
+                t *= s;
+            }
+            s *= s;
+            n >>= 1;
+        }
+        t
+    }
+}
+
+impl<T> QuadExt<T>
+where
+    T: Copy + Sub<Output = T> + Mul<Output = T> + Neg<Output = T>,
+{
+    // a - b sqrt(d)
+    pub fn conj(self) -> Self {
+        Self::new(self.a, -self.b, self.d)
+    }
+
+    // (a + b sqrt(d)) (a - b sqrt(d)) = a^2 - d b^2
+    pub fn norm(self) -> T {
+        self.a * self.a - self.d * self.b * self.b
+    }
+}
+
+impl<T> QuadExt<T>
+where
+    T: Copy + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+{
+    // conj / norm (norm should be invertible in T)
+    pub fn inv(self) -> Self {
+        let n = self.norm();
+        Self::new(self.a / n, -self.b / n, self.d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint::{Mint, Mint998244353, StaticModInt};
+
+    fn fibonacci<const M: u32>(n: u64) -> StaticModInt<M> {
+        // Binet's formula: F_n = (phi^n - psi^n) / sqrt(5), where phi, psi = (1 +- sqrt(5)) / 2
+        let half = StaticModInt::new(2).inv();
+        let phi = QuadExt::new(half, half, StaticModInt::new(5));
+        let f = phi.pow(n) - phi.conj().pow(n);
+        assert_eq!(f.a, 0);
+        f.b
+    }
+
+    #[test]
+    fn test_binet() {
+        let (mut a, mut b) = (0u64, 1u64);
+        for n in 0..90 {
+            assert_eq!(fibonacci::<1_000_000_007>(n), Mint::from(a));
+            assert_eq!(fibonacci::<998_244_353>(n), Mint998244353::from(a));
+            (a, b) = (b, a + b);
+        }
+
+        assert_eq!(
+            fibonacci::<1_000_000_007>(1_000_000_000_000_000_000),
+            209_783_453
+        );
+        assert_eq!(
+            fibonacci::<998_244_353>(1_000_000_000_000_000_000),
+            23_849_548
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let d = Mint::new(7);
+        let x = QuadExt::new(Mint::new(3), Mint::new(2), d);
+        let y = QuadExt::new(Mint::new(5), -Mint::new(1), d);
+
+        assert_eq!(x + y, QuadExt::new(Mint::new(8), Mint::new(1), d));
+        assert_eq!(x - y, QuadExt::new(-Mint::new(2), Mint::new(3), d));
+        assert_eq!(-x, QuadExt::new(-Mint::new(3), -Mint::new(2), d));
+        // (3 + 2 sqrt(7)) (5 - sqrt(7)) = 15 - 14 + (10 - 3) sqrt(7)
+        assert_eq!(x * y, QuadExt::new(Mint::new(1), Mint::new(7), d));
+        assert_eq!(x * y / y, x);
+        assert_eq!(x * x.inv(), QuadExt::from_base(Mint::new(1), d));
+        assert_eq!(x.norm(), Mint::new(9) - Mint::new(28));
+        assert_eq!(x * x.conj(), QuadExt::from_base(x.norm(), d));
+        assert_eq!(x.pow(0), QuadExt::from_base(Mint::new(1), d));
+        assert_eq!(x.pow(3), x * x * x);
+
+        let mut z = x;
+        z += y;
+        z -= y;
+        z *= y;
+        z /= y;
+        assert_eq!(z, x);
+    }
+
+    #[test]
+    fn test_cipolla() {
+        // sqrt(n) = (r + sqrt(r^2 - n))^((p + 1) / 2), where r^2 - n is a non-residue
+        let p = Mint::MODULUS as u64;
+        for n in [4, 2, 123_456_789] {
+            let n = Mint::new(n);
+            let r = (1..)
+                .map(Mint::new)
+                .find(|r| (*r * *r - n).pow((Mint::MODULUS - 1) / 2) == Mint::MODULUS - 1)
+                .unwrap();
+            let x = QuadExt::new(r, Mint::new(1), r * r - n).pow((p + 1) / 2);
+            assert_eq!(x.b, 0);
+            assert_eq!(x.a * x.a, n);
+        }
+    }
+}
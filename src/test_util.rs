@@ -0,0 +1,10 @@
+// Helpers shared by tests of several modules
+
+use crate::mint::StaticModInt;
+
+// Pseudo-random sequence of length n
+pub(crate) fn sequence<const M: u32>(n: usize, seed: u64) -> Vec<StaticModInt<M>> {
+    (0..n as u64)
+        .map(|i| StaticModInt::new(((i * 7919 + seed) * (i + seed) % M as u64) as u32))
+        .collect()
+}